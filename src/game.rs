@@ -1,70 +1,208 @@
+mod animation;
+mod background;
+mod biome;
+mod bounds;
+mod camera;
+mod checkpoint;
+mod daily;
+mod drones;
+mod dynamics;
+mod fields;
+mod fuel;
+mod ghost;
+mod heat;
+mod hud;
+mod hull;
+mod level;
+mod menu;
+mod modifiers;
+mod particles;
+mod physics;
+mod pickup;
 mod player;
+mod profile;
+mod radar;
+mod score;
+mod ship;
+mod tutorial;
 mod ui;
 
-use crate::{loading::TextureAssets, GameState};
-use bevy::{
-    input::{keyboard::KeyCode, Input},
-    prelude::*,
-    sprite::MaterialMesh2dBundle,
+use crate::{
+    loading::{SheetAssets, TextureAssets},
+    GameState,
 };
-use bevy_rapier2d::{prelude::*, rapier::dynamics::RigidBodyBuilder};
+pub use animation::{AnimationClips, AnimationState};
+use bevy::{prelude::*, utils::HashSet};
+use bevy_rapier2d::prelude::*;
 use bevy_turborand::{prelude::*, *};
-pub use player::{
-    MaxScore, PlayerCoolingTimer, PlayerFuel, PlayerLabel, PlayerScore,
+pub use biome::{Biome, BiomeChanged, CurrentBiome};
+pub use bounds::{PlayerWrapped, WorldEdge, WORLD_HALF_WIDTH};
+pub use camera::PlayerCamera;
+pub use checkpoint::{
+    CheckpointPlatform, CheckpointReached, Checkpoints, CHECKPOINT_INTERVAL,
 };
-use std::time::Duration;
+pub use daily::DailyChallenge;
+pub use drones::Drone;
+pub use dynamics::FlightDynamics;
+pub use fields::FieldKind;
+pub use fuel::{FuelEconomy, PlayerFuel};
+pub use ghost::Ghost;
+pub use heat::{CoolingModel, HeatLevel, HeatWarning, LastHeatLevel};
+pub use hull::{Hazard, Hull};
+pub use level::LevelObstacle;
+pub use modifiers::ActiveModifiers;
+pub use particles::{
+    spawn_particle, ExhaustEmitter, Particle, ParticleSpec, TrailEmitter,
+    MAX_PARTICLES,
+};
+pub use physics::{
+    FixedPhysicsStage, PoseInterpolation, PHYSICS_DT, PIXELS_PER_METER,
+};
+pub use pickup::{FuelMagnet, FuelPickedUp};
+pub use player::{FlightModel, PlayerCoolingTimer, PlayerLabel, PlayerThrust};
+pub use profile::PlayerProfile;
+pub use radar::MinimapMode;
+pub use score::{meters, Combo, MaxScore, NearMiss, PlayerScore};
+pub use ship::Ship;
+pub use tutorial::Tutorial;
 
-struct PlayerCollisions {
-    entities: Vec<(Entity, Entity)>,
-}
 #[derive(Component)]
 pub struct GameEntity;
+/// Which kind of run is started when entering [`GameState::Game`]
+#[derive(Clone, Copy, Eq, PartialEq, Debug)]
+pub enum GameMode {
+    Classic,
+    DailyChallenge,
+    /// a random [`Modifier`] is applied every 10 seconds
+    RuleShift,
+    /// scripted first run that walks through the basics
+    Tutorial,
+}
 pub struct GamePlugin;
 
 impl Plugin for GamePlugin {
     fn build(&self, app: &mut App) {
-        app.add_plugin(RapierPhysicsPlugin::<NoUserData>::pixels_per_meter(
-            100.0,
-        ))
-        .insert_resource(FuelCurrentlySpawned::new(1000))
-        .add_plugin(ui::UiPlugin)
-        .add_plugin(RngPlugin::default())
-        .add_plugin(RapierDebugRenderPlugin::default())
-        .add_plugin(player::PlayerPlugin)
-        .add_system_set(
-            SystemSet::on_enter(GameState::Game)
-                .with_system(spawn_scene)
-                .with_system(insert_spawn),
-        )
-        .add_system_set(
-            SystemSet::on_update(GameState::Game)
-                .with_system(handle_collision)
-                .with_system(tick_spawn)
-                .with_system(clean_up_fuel),
-        )
-        .add_system_to_stage(CoreStage::PostUpdate, collision)
-        .add_system_set(
-            SystemSet::on_exit(GameState::Game)
-                .with_system(despawn_entity)
-                .with_system(reset_fuel_spawn),
-        );
+        app.add_plugin(physics::PhysicsPlugin)
+            .insert_resource(FuelCurrentlySpawned::new(1000))
+            .insert_resource(FuelBands::default())
+            .insert_resource(GameMode::Classic)
+            .insert_resource(RunSeed(0))
+            .add_plugin(ui::UiPlugin)
+            .add_plugin(hud::HudPlugin)
+            .add_plugin(radar::RadarPlugin)
+            .add_plugin(ghost::GhostPlugin)
+            .add_plugin(menu::MenuPlugin)
+            .add_plugin(daily::DailyPlugin)
+            .add_plugin(modifiers::ModifierPlugin)
+            .add_plugin(RngPlugin::default())
+            .add_plugin(RapierDebugRenderPlugin::default())
+            .add_plugin(player::PlayerPlugin)
+            .add_plugin(score::ScorePlugin)
+            .add_plugin(fuel::FuelPlugin)
+            .add_plugin(hull::HullPlugin)
+            .add_plugin(drones::DronePlugin)
+            .add_plugin(heat::HeatPlugin)
+            .add_plugin(ship::ShipPlugin)
+            .add_plugin(profile::ProfilePlugin)
+            .add_plugin(dynamics::DynamicsPlugin)
+            .add_plugin(camera::CameraPlugin)
+            .add_plugin(bounds::BoundsPlugin)
+            .add_plugin(level::LevelPlugin)
+            .add_plugin(checkpoint::CheckpointPlugin)
+            .add_plugin(fields::FieldPlugin)
+            .add_plugin(biome::BiomePlugin)
+            .add_plugin(background::BackgroundPlugin)
+            .add_plugin(particles::ParticlePlugin)
+            .add_plugin(pickup::PickupPlugin)
+            .add_plugin(animation::AnimationPlugin)
+            .add_plugin(tutorial::TutorialPlugin)
+            .add_system_set(
+                SystemSet::on_enter(GameState::Game)
+                    .with_system(
+                        choose_run_seed
+                            .label(GameLabel::RunSeed)
+                            .after(GameLabel::Checkpoint),
+                    )
+                    .with_system(spawn_scene),
+            )
+            .add_system_set(
+                SystemSet::on_update(GameState::Game)
+                    .with_system(handle_collision)
+                    .with_system(stream_fuel)
+                    .with_system(drift_fuel)
+                    .with_system(clean_up_fuel),
+            )
+            .add_system_to_stage(CoreStage::PostUpdate, count_removed_fuel)
+            .add_system_set(
+                SystemSet::on_exit(GameState::Game)
+                    .with_system(despawn_entity)
+                    .with_system(reset_fuel_spawn),
+            );
     }
 }
-#[derive(Component, Clone, Copy, PartialEq)]
-pub enum CollisionTag {
-    Collided,
-    NotCollided,
+#[derive(SystemLabel, Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum GameLabel {
+    /// picks the [`RunSeed`] when a run starts
+    RunSeed,
+    /// ends the run when the ship runs out of cooling
+    Cooling,
+    /// sets the thrust force of the ship for the frame
+    Thrust,
+    /// decides whether a run starts from a checkpoint
+    Checkpoint,
+}
+/// Seed for everything generated during a run. Fixed for the whole day in
+/// the daily challenge so every player gets the same run.
+pub struct RunSeed(pub u64);
+impl RunSeed {
+    /// derives an independent seed for one part of the run, such as a
+    /// single level chunk
+    pub fn derive(&self, salt: u64) -> u64 {
+        mix_seed(self.0 ^ mix_seed(salt))
+    }
+}
+/// splitmix64 finalizer, turns neighbouring inputs into unrelated seeds
+pub fn mix_seed(seed: u64) -> u64 {
+    let mut z = seed.wrapping_add(0x9E37_79B9_7F4A_7C15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
+}
+/// blends from `a` at `t = 0.0` to `b` at `t = 1.0`, alpha included
+pub(crate) fn lerp_color(a: Color, b: Color, t: f32) -> Color {
+    Color::rgba(
+        a.r() + (b.r() - a.r()) * t,
+        a.g() + (b.g() - a.g()) * t,
+        a.b() + (b.b() - a.b()) * t,
+        a.a() + (b.a() - a.a()) * t,
+    )
+}
+/// seed of the level the tutorial is played on
+const TUTORIAL_SEED: u64 = 0x5475_746f_7269_616c;
+fn choose_run_seed(
+    mut run_seed: ResMut<RunSeed>,
+    mut global_rng: ResMut<GlobalRng>,
+    game_mode: Res<GameMode>,
+    daily: Res<DailyChallenge>,
+    checkpoints: Res<Checkpoints>,
+) {
+    // a continued run climbs the same level again
+    if let Some(snapshot) = checkpoints.resumed() {
+        run_seed.0 = snapshot.seed;
+        return;
+    }
+    run_seed.0 = match *game_mode {
+        GameMode::DailyChallenge => daily.seed(),
+        // the tutorial climbs the same level every time
+        GameMode::Tutorial => TUTORIAL_SEED,
+        GameMode::Classic | GameMode::RuleShift => global_rng.u64(..),
+    };
 }
 #[derive(Component)]
 pub struct FuelTag;
 #[derive(Bundle, Default)]
 struct FuelBundle {}
-fn spawn_scene(
-    mut commands: Commands,
-    mut meshes: ResMut<Assets<Mesh>>,
-    mut materials: ResMut<Assets<ColorMaterial>>,
-    textures: Res<TextureAssets>,
-) {
+fn spawn_scene(mut commands: Commands, textures: Res<TextureAssets>) {
     let mut transform =
         Transform::from_translation(Vec3::new(0.0, -300.0, 0.0));
     transform.scale = Vec3::new(4.0, 4.0, 4.0);
@@ -85,85 +223,95 @@ fn despawn_entity(
         commands.entity(entity).despawn_recursive();
     }
 }
+#[allow(clippy::too_many_arguments)]
 fn handle_collision(
     mut commands: Commands,
-    mut set: ParamSet<(
-        Query<(&mut PlayerFuel, &mut PlayerCoolingTimer, &CollisionTag), ()>,
-        Query<(Entity, &CollisionTag), With<FuelTag>>,
-        Query<&mut CollisionTag, ()>,
-    )>,
+    mut player_query: Query<
+        (Entity, &mut PlayerFuel, &mut PlayerCoolingTimer),
+        With<PlayerLabel>,
+    >,
+    fuel_query: Query<(&Transform, Option<&FuelSlot>), With<FuelTag>>,
+    mut collision_events: EventReader<CollisionEvent>,
+    mut pickup_events: EventWriter<FuelPickedUp>,
+    mut bands: ResMut<FuelBands>,
+    cooling_model: Res<CoolingModel>,
+    economy: Res<FuelEconomy>,
 ) {
-    for (mut fuel, mut player_cooling, tag) in set.p0().iter_mut() {
-        if *tag == CollisionTag::Collided {
-            fuel.add_fuel(100.0);
-            player_cooling.refill_cooling();
+    let (player, mut fuel, mut player_cooling) =
+        match player_query.iter_mut().next() {
+            Some(player) => player,
+            None => {
+                error!("player not found");
+                return;
+            }
+        };
+    // only pickups the ship touched refuel, bumping into the ground or
+    // obstacles doesn't
+    let mut collected = Vec::new();
+    for event in collision_events.iter() {
+        let other = match event {
+            CollisionEvent::Started(e1, e2, _) if *e1 == player => *e2,
+            CollisionEvent::Started(e1, e2, _) if *e2 == player => *e1,
+            _ => continue,
+        };
+        if collected.contains(&other) {
+            continue;
         }
-    }
-    for (entity, tag) in set.p1().iter() {
-        if *tag == CollisionTag::Collided {
-            commands.entity(entity).despawn();
+        let (position, slot) = match fuel_query.get(other) {
+            Ok((transform, slot)) => (transform.translation.truncate(), slot),
+            Err(_) => continue,
+        };
+        collected.push(other);
+        if let Some(slot) = slot {
+            bands.collected.insert(*slot);
         }
-    }
-    for mut tag in set.p2().iter_mut() {
-        *tag = CollisionTag::NotCollided
+        commands.entity(other).despawn();
+        let amount = fuel.add_fuel(economy.pickup_value(position.y));
+        if *cooling_model == CoolingModel::Timer {
+            player_cooling.refill_cooling();
+        }
+        pickup_events.send(FuelPickedUp { position, amount });
     }
 }
-fn collision(
-    mut commands: Commands,
-    mut collision_event: EventReader<CollisionEvent>,
+/// counts pickups once they are actually gone, a pickup collected in the
+/// same frame it is cleaned up is despawned twice but removed once
+fn count_removed_fuel(
+    removed: RemovedComponents<FuelTag>,
+    mut fuel_spawn: ResMut<FuelCurrentlySpawned>,
 ) {
-    for collision in collision_event.iter() {
-        match collision {
-            CollisionEvent::Started(e1, e2, f) => {
-                commands.entity(*e1).insert(CollisionTag::Collided);
-                commands.entity(*e2).insert(CollisionTag::Collided);
-                info!("collision started")
-            }
-            CollisionEvent::Stopped(e1, e12, f) => info!("ended"),
-        }
-    }
+    let count = removed.iter().count() as u32;
+    // the counter is reset when a run ends, before its pickups are removed
+    fuel_spawn.spawned = fuel_spawn.spawned.saturating_sub(count);
 }
 
-#[derive(Default, Component)]
-struct FuelSpawnerLabel;
-#[derive(Component)]
-struct FuelSpawner {
-    timer: Timer,
+/// Which pickup of which band a streamed pickup is, so a collected pickup
+/// stays collected when its band is streamed in again
+#[derive(Component, Clone, Copy, PartialEq, Eq, Hash, Debug)]
+struct FuelSlot {
+    band: i32,
+    index: usize,
 }
-impl FuelSpawner {
-    const RESPAWN_TIME_SEC: f32 = 1.0;
+/// Pickups are laid out in bands of [`FUEL_BAND_HEIGHT`] seeded from the
+/// [`RunSeed`], so the same seed always gives the same pickups
+#[derive(Default)]
+struct FuelBands {
+    loaded: HashSet<i32>,
+    collected: HashSet<FuelSlot>,
 }
-impl Default for FuelSpawner {
-    fn default() -> Self {
-        Self {
-            timer: Timer::from_seconds(Self::RESPAWN_TIME_SEC, false),
-        }
+impl FuelBands {
+    /// pickups in a band near the ground, higher bands get fewer
+    const PICKUPS_PER_BAND: f32 = 40.0;
+    fn pickup_count(band: i32) -> usize {
+        let bottom = band as f32 * FUEL_BAND_HEIGHT;
+        (Self::PICKUPS_PER_BAND * 100.0_f32.ln() / bottom.max(100.0).ln())
+            as usize
     }
 }
-#[derive(Bundle, Default)]
-struct FuelSpawnerBundle {
-    spawner: FuelSpawner,
-    label: FuelSpawnerLabel,
-    rng: RngComponent,
-}
-fn spawn_fn(x: f32) -> f32 {
-    1.0 / x
-}
+/// height of a band of pickups
+const FUEL_BAND_HEIGHT: f32 = 1000.0;
+/// salt for the [`RunSeed`] of pickup placement
+const FUEL_SEED: u64 = 0x4655_454c;
 const FUEL_RADIUS: f32 = 10.0;
-fn insert_spawn(
-    mut commands: Commands,
-    mut global_rng: ResMut<GlobalRng>,
-    mut meshes: ResMut<Assets<Mesh>>,
-    mut fuel_spawn: ResMut<FuelCurrentlySpawned>,
-    mut materials: ResMut<Assets<ColorMaterial>>,
-    textures: Res<TextureAssets>,
-) {
-    commands.spawn_bundle(FuelSpawnerBundle {
-        label: FuelSpawnerLabel,
-        rng: RngComponent::from(&mut global_rng),
-        ..default()
-    });
-}
 struct FuelCurrentlySpawned {
     spawned: u32,
     max_spawn: u32,
@@ -176,66 +324,120 @@ impl FuelCurrentlySpawned {
         }
     }
 }
-fn tick_spawn(
+/// loads the pickups of the bands near the player and unloads the rest
+#[allow(clippy::too_many_arguments)]
+fn stream_fuel(
     mut commands: Commands,
-    mut query: Query<(&mut FuelSpawner, &mut RngComponent), ()>,
+    mut bands: ResMut<FuelBands>,
     player_query: Query<&Transform, With<PlayerLabel>>,
-    time: Res<Time>,
-    mut meshes: ResMut<Assets<Mesh>>,
+    fuel_query: Query<(Entity, &FuelSlot)>,
+    run_seed: Res<RunSeed>,
     mut fuel_spawn: ResMut<FuelCurrentlySpawned>,
-    mut materials: ResMut<Assets<ColorMaterial>>,
-    textures: Res<TextureAssets>,
+    sheets: Res<SheetAssets>,
+    edge: Res<WorldEdge>,
+    tutorial: Res<Tutorial>,
 ) {
-    let player_transform = player_query.iter().next();
-    if player_transform.is_none() {
-        error!("player does not exist");
+    // the tutorial places its own pickups
+    if tutorial.places_pickups() {
         return;
     }
-    let player_transform = player_transform.unwrap();
-    for (mut spawner, mut rng) in query.iter_mut() {
-        if fuel_spawn.spawned >= fuel_spawn.max_spawn {
+    let player_y = match player_query.iter().next() {
+        Some(transform) => transform.translation.y,
+        None => {
+            error!("player not found");
             return;
         }
-        fuel_spawn.spawned += 1;
-        spawner.timer.tick(time.delta());
-        if spawner.timer.finished() {
-            let x = 500.0 * rng.f32_normalized();
-
-            let y =
-                1000.0 * rng.f32_normalized() + player_transform.translation.y;
-            let next_spawn = rng.f32()
-                * player_transform.translation.y.max(100.0).ln()
-                / 100.0;
-            info!("next spawn: {}", next_spawn);
-            let mut transform =
-                Transform::from_translation(Vec3::new(x, y, 0.0));
-            transform.scale = Vec3::new(4.0, 4.0, 4.0);
-            spawner.timer.reset();
-            spawner
-                .timer
-                .set_duration(Duration::from_secs_f32(next_spawn));
-            commands
-                .spawn_bundle(SpriteBundle {
-                    texture: textures.fuel.clone(),
-                    transform,
-                    ..default()
-                })
-                .insert(Collider::ball(FUEL_RADIUS))
-                .insert(ActiveEvents::all())
-                .insert(Sensor)
-                .insert(FuelTag)
-                .insert(GameEntity);
+    };
+    let lowest =
+        ((player_y - FUEL_MAX_Y_DIST) / FUEL_BAND_HEIGHT).floor() as i32;
+    let highest =
+        ((player_y + FUEL_MAX_Y_DIST) / FUEL_BAND_HEIGHT).floor() as i32;
+    let range = lowest..=highest;
+    for (entity, slot) in fuel_query.iter() {
+        if !range.contains(&slot.band) {
+            commands.entity(entity).despawn_recursive();
         }
     }
+    bands.loaded.retain(|band| range.contains(band));
+    for band in lowest.max(0)..=highest {
+        if !bands.loaded.insert(band) {
+            continue;
+        }
+        let rng = Rng::with_seed(run_seed.derive(FUEL_SEED ^ band as u64));
+        let bottom = band as f32 * FUEL_BAND_HEIGHT;
+        for index in 0..FuelBands::pickup_count(band) {
+            // roll every pickup so collected ones don't shift the rest
+            let position = Vec2::new(
+                edge.spawn_half_width() * rng.f32_normalized(),
+                bottom + FUEL_BAND_HEIGHT * rng.f32(),
+            );
+            let frame = rng.usize(..8);
+            let slot = FuelSlot { band, index };
+            if bands.collected.contains(&slot)
+                || fuel_spawn.spawned >= fuel_spawn.max_spawn
+            {
+                continue;
+            }
+            let entity = spawn_fuel(
+                &mut commands,
+                &mut fuel_spawn,
+                &sheets,
+                position,
+                frame,
+            );
+            commands.entity(entity).insert(slot);
+        }
+    }
+}
+/// spawns a fuel pickup at `position`, `frame` staggers the spin animation
+/// between pickups
+fn spawn_fuel(
+    commands: &mut Commands,
+    fuel_spawn: &mut FuelCurrentlySpawned,
+    sheets: &SheetAssets,
+    position: Vec2,
+    frame: usize,
+) -> Entity {
+    fuel_spawn.spawned += 1;
+    let mut transform = Transform::from_translation(position.extend(0.0));
+    transform.scale = Vec3::new(4.0, 4.0, 4.0);
+    commands
+        .spawn_bundle(SpriteSheetBundle {
+            texture_atlas: sheets.fuel.clone(),
+            transform,
+            ..default()
+        })
+        .insert(AnimationState::new("fuel_spin").with_frame(frame))
+        .insert(Collider::ball(FUEL_RADIUS))
+        .insert(ActiveEvents::all())
+        .insert(Sensor)
+        .insert(FuelTag)
+        .insert(GameEntity)
+        .id()
+}
+/// moves pickups sideways while [`Modifier::PickupDrift`] is active
+fn drift_fuel(
+    mut fuel_query: Query<&mut Transform, With<FuelTag>>,
+    modifiers: Res<ActiveModifiers>,
+    time: Res<Time>,
+) {
+    let drift = modifiers.pickup_drift() * time.delta_seconds();
+    if drift == 0.0 {
+        return;
+    }
+    for mut transform in fuel_query.iter_mut() {
+        transform.translation.x += drift;
+    }
 }
 const FUEL_MAX_Y_DIST: f32 = 1000.0;
+#[allow(clippy::type_complexity)]
 fn clean_up_fuel(
     mut commands: Commands,
     mut p_set: ParamSet<(
-        Query<(Entity, &Transform), With<FuelTag>>,
+        Query<(Entity, &mut Transform, Option<&FuelSlot>), With<FuelTag>>,
         Query<&Transform, With<PlayerLabel>>,
     )>,
-    mut fuel_spawn: ResMut<FuelCurrentlySpawned>,
+    edge: Res<WorldEdge>,
 ) {
     let player_query = p_set.p1();
     let player_transform = player_query.iter().next();
@@ -245,15 +447,20 @@ fn clean_up_fuel(
     }
     let player_transform = player_transform.unwrap();
     let player_y = player_transform.translation.y;
-    let fuel_query = p_set.p0();
-    for (entity, fuel_transform) in fuel_query.iter() {
+    let mut fuel_query = p_set.p0();
+    for (entity, mut fuel_transform, slot) in fuel_query.iter_mut() {
         let fuel_y = fuel_transform.translation.y;
-        if (fuel_y - player_y).abs() > FUEL_MAX_Y_DIST {
+        // streamed pickups are unloaded with their band
+        let far = slot.is_none() && (fuel_y - player_y).abs() > FUEL_MAX_Y_DIST;
+        if far || !edge.contain(&mut fuel_transform.translation) {
             commands.entity(entity).despawn_recursive();
-            fuel_spawn.spawned -= 1;
         }
     }
 }
-fn reset_fuel_spawn(mut counter: ResMut<FuelCurrentlySpawned>) {
+fn reset_fuel_spawn(
+    mut counter: ResMut<FuelCurrentlySpawned>,
+    mut bands: ResMut<FuelBands>,
+) {
     counter.spawned = 0;
+    *bands = FuelBands::default();
 }
@@ -0,0 +1,276 @@
+use super::{
+    FuelMagnet, FuelTag, GameState, PlayerCoolingTimer, PlayerLabel,
+    PlayerThrust,
+};
+use crate::loading::AnimationAssets;
+use bevy::{
+    asset::{AssetLoader, LoadContext, LoadedAsset},
+    prelude::*,
+    reflect::TypeUuid,
+    utils::{BoxedFuture, HashMap},
+};
+
+pub struct AnimationPlugin;
+impl Plugin for AnimationPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_asset::<AnimationClips>()
+            .init_asset_loader::<AnimationClipsLoader>()
+            .add_system_set(
+                SystemSet::on_update(GameState::Game)
+                    .with_system(choose_ship_clip.before(advance_animations))
+                    .with_system(choose_fuel_clip.before(advance_animations))
+                    .with_system(advance_animations),
+            );
+    }
+}
+/// A run of frames in a sprite sheet
+#[derive(Clone, Copy, Debug)]
+pub struct AnimationClip {
+    pub first: usize,
+    pub frames: usize,
+    pub fps: f32,
+    /// starts over after the last frame instead of holding it
+    pub looping: bool,
+}
+impl AnimationClip {
+    /// whether every frame of the clip is in a sheet of `sheet_len` frames
+    pub fn fits(&self, sheet_len: usize) -> bool {
+        self.first + self.frames <= sheet_len
+    }
+}
+/// Named clips loaded from a `.anim` file, one clip per line:
+/// `name first_frame frame_count fps loop|once`
+#[derive(TypeUuid, Debug)]
+#[uuid = "4b5d2a8e-9d3c-4f51-a0e2-6c1f7b3e8d90"]
+pub struct AnimationClips {
+    clips: HashMap<String, AnimationClip>,
+}
+impl AnimationClips {
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let mut clips = HashMap::default();
+        for (number, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let (name, clip) = Self::parse_line(line).ok_or_else(|| {
+                format!("invalid animation clip on line {}", number + 1)
+            })?;
+            clips.insert(name.to_string(), clip);
+        }
+        Ok(Self { clips })
+    }
+    fn parse_line(line: &str) -> Option<(&str, AnimationClip)> {
+        let fields: Vec<&str> = line.split_whitespace().collect();
+        match fields[..] {
+            [name, first, frames, fps, mode] => Some((
+                name,
+                AnimationClip {
+                    first: first.parse().ok()?,
+                    frames: frames.parse().ok().filter(|f| *f > 0)?,
+                    fps: fps.parse().ok().filter(|f| *f > 0.0)?,
+                    looping: match mode {
+                        "loop" => true,
+                        "once" => false,
+                        _ => return None,
+                    },
+                },
+            )),
+            _ => None,
+        }
+    }
+    pub fn get(&self, name: &str) -> Option<&AnimationClip> {
+        self.clips.get(name)
+    }
+}
+#[derive(Default)]
+struct AnimationClipsLoader;
+impl AssetLoader for AnimationClipsLoader {
+    fn load<'a>(
+        &'a self,
+        bytes: &'a [u8],
+        load_context: &'a mut LoadContext,
+    ) -> BoxedFuture<'a, Result<(), bevy::asset::Error>> {
+        Box::pin(async move {
+            let clips = AnimationClips::parse(std::str::from_utf8(bytes)?)
+                .map_err(bevy::asset::Error::msg)?;
+            load_context.set_default_asset(LoadedAsset::new(clips));
+            Ok(())
+        })
+    }
+    fn extensions(&self) -> &[&str] {
+        &["anim"]
+    }
+}
+/// Clip a sprite sheet is playing and how far into it it is
+#[derive(Component)]
+pub struct AnimationState {
+    clip: &'static str,
+    frame: usize,
+    elapsed: f32,
+    /// playback rate, 1.0 plays the clip at its own fps
+    pub speed: f32,
+}
+impl AnimationState {
+    pub fn new(clip: &'static str) -> Self {
+        Self {
+            clip,
+            frame: 0,
+            elapsed: 0.0,
+            speed: 1.0,
+        }
+    }
+    /// starts at `frame` instead of the first frame, so pickups spawned
+    /// together don't spin in sync
+    pub fn with_frame(mut self, frame: usize) -> Self {
+        self.frame = frame;
+        self
+    }
+    /// switches to `clip`, restarting it only if it wasn't already playing
+    pub fn play(&mut self, clip: &'static str) {
+        if self.clip != clip {
+            self.clip = clip;
+            self.frame = 0;
+            self.elapsed = 0.0;
+        }
+    }
+}
+/// cooling used before the ship starts glowing
+const OVERHEAT_FRAC: f32 = 0.7;
+fn choose_ship_clip(
+    mut player_query: Query<
+        (&mut AnimationState, &PlayerThrust, &PlayerCoolingTimer),
+        With<PlayerLabel>,
+    >,
+) {
+    for (mut animation, thrust, cooling) in player_query.iter_mut() {
+        let heat = cooling.get_frac_used();
+        if heat >= OVERHEAT_FRAC {
+            animation.play("ship_overheat");
+            // glows faster the closer the ship gets to overheating
+            animation.speed =
+                1.0 + 3.0 * (heat - OVERHEAT_FRAC) / (1.0 - OVERHEAT_FRAC);
+        } else if thrust.is_thrusting() {
+            animation.play("ship_thrust");
+            animation.speed = 1.0;
+        } else {
+            animation.play("ship_idle");
+            animation.speed = 1.0;
+        }
+    }
+}
+/// pickups pulse while the magnet is pulling them in
+fn choose_fuel_clip(
+    player_query: Query<(&Transform, &FuelMagnet), With<PlayerLabel>>,
+    mut fuel_query: Query<(&Transform, &mut AnimationState), With<FuelTag>>,
+) {
+    let (player_transform, magnet) = match player_query.iter().next() {
+        Some(player) => player,
+        None => {
+            error!("player not found");
+            return;
+        }
+    };
+    let player_position = player_transform.translation.truncate();
+    for (transform, mut animation) in fuel_query.iter_mut() {
+        let distance =
+            transform.translation.truncate().distance(player_position);
+        if distance < magnet.radius {
+            animation.play("fuel_pulse");
+        } else {
+            animation.play("fuel_spin");
+        }
+    }
+}
+fn advance_animations(
+    mut query: Query<(
+        &mut AnimationState,
+        &mut TextureAtlasSprite,
+        &Handle<TextureAtlas>,
+    )>,
+    animations: Res<AnimationAssets>,
+    clips: Res<Assets<AnimationClips>>,
+    atlases: Res<Assets<TextureAtlas>>,
+    time: Res<Time>,
+) {
+    let clips = match clips.get(&animations.clips) {
+        Some(clips) => clips,
+        None => return,
+    };
+    for (mut animation, mut sprite, atlas) in query.iter_mut() {
+        let clip = match clips.get(animation.clip) {
+            Some(clip) => *clip,
+            None => {
+                error!("unknown animation clip {}", animation.clip);
+                continue;
+            }
+        };
+        let atlas_len = match atlases.get(atlas) {
+            Some(atlas) => atlas.len(),
+            None => continue,
+        };
+        // a clip running past the end of its sheet would index out of range
+        if !clip.fits(atlas_len) {
+            error!(
+                "animation clip {} runs past the {} frames of its sheet",
+                animation.clip, atlas_len
+            );
+            continue;
+        }
+        animation.elapsed += time.delta_seconds() * animation.speed;
+        let frame_time = 1.0 / clip.fps;
+        while animation.elapsed >= frame_time {
+            animation.elapsed -= frame_time;
+            animation.frame += 1;
+        }
+        if clip.looping {
+            animation.frame %= clip.frames;
+        } else {
+            animation.frame = animation.frame.min(clip.frames - 1);
+        }
+        sprite.index = clip.first + animation.frame;
+    }
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_a_clip_line() {
+        let clips = AnimationClips::parse(
+            "# comment\n\nship_thrust 2 2 16 loop\nexplode 4 3 8 once\n",
+        )
+        .unwrap();
+        let thrust = clips.get("ship_thrust").unwrap();
+        assert_eq!(thrust.first, 2);
+        assert_eq!(thrust.frames, 2);
+        assert_eq!(thrust.fps, 16.0);
+        assert!(thrust.looping);
+        assert!(!clips.get("explode").unwrap().looping);
+        assert!(clips.get("ship_idle").is_none());
+    }
+    #[test]
+    fn rejects_a_wrong_field_count() {
+        assert!(AnimationClips::parse("ship_idle 0 2 2").is_err());
+        assert!(AnimationClips::parse("ship_idle 0 2 2 loop extra").is_err());
+    }
+    #[test]
+    fn rejects_a_clip_without_frames() {
+        assert!(AnimationClips::parse("ship_idle 0 0 2 loop").is_err());
+        assert!(AnimationClips::parse("ship_idle 0 2 0 loop").is_err());
+    }
+    #[test]
+    fn rejects_an_unknown_mode() {
+        let error =
+            AnimationClips::parse("ship_idle 0 2 2 loop\nfuel 0 8 10 bounce")
+                .unwrap_err();
+        assert!(error.contains("line 2"));
+    }
+    #[test]
+    fn clip_fits_only_inside_its_sheet() {
+        let clips = AnimationClips::parse("fuel_pulse 8 4 12 loop").unwrap();
+        let pulse = clips.get("fuel_pulse").unwrap();
+        assert!(pulse.fits(12));
+        assert!(!pulse.fits(11));
+    }
+}
@@ -0,0 +1,322 @@
+use super::{
+    biome::BiomeLabel, camera::CameraLabel, Biome, CurrentBiome, GameEntity,
+    GameState, PlayerCamera,
+};
+use bevy::{prelude::*, sprite::MaterialMesh2dBundle};
+use bevy_turborand::*;
+
+pub struct BackgroundPlugin;
+impl Plugin for BackgroundPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<BackgroundAssets>()
+            .add_system_set(
+                SystemSet::on_enter(GameState::Game).with_system(spawn_layers),
+            )
+            .add_system_set(
+                SystemSet::on_update(GameState::Game)
+                    .with_system(scroll_parallax.after(CameraLabel::Shake))
+                    .with_system(fade_layers.after(BiomeLabel)),
+            );
+    }
+}
+/// Size of the area background elements repeat over, larger than the
+/// zoomed out screen so the repetition is not noticeable
+const TILE_SIZE: Vec2 = Vec2::new(4000.0, 2400.0);
+/// Depth of the furthest layer, the 2d camera only shows z above -0.1
+const BACKGROUND_Z: f32 = -0.09;
+/// What the elements of a layer look like
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum LayerShape {
+    /// small squares scattered everywhere
+    Star,
+    /// puffs made of overlapping circles
+    Cloud,
+    /// triangles standing on the ground, they only repeat sideways
+    Mountain,
+}
+/// Data describing one parallax layer
+pub struct LayerDef {
+    pub shape: LayerShape,
+    pub count: usize,
+    /// 0.0 stays fixed to the screen, 1.0 moves with the world
+    pub factor: f32,
+    pub color: Color,
+    pub min_size: f32,
+    pub max_size: f32,
+}
+const LOWER_ATMOSPHERE_LAYERS: &[LayerDef] = &[
+    LayerDef {
+        shape: LayerShape::Mountain,
+        count: 12,
+        factor: 0.1,
+        color: Color::rgb(0.3, 0.4, 0.55),
+        min_size: 300.0,
+        max_size: 600.0,
+    },
+    LayerDef {
+        shape: LayerShape::Cloud,
+        count: 10,
+        factor: 0.2,
+        color: Color::rgba(1.0, 1.0, 1.0, 0.5),
+        min_size: 40.0,
+        max_size: 70.0,
+    },
+    LayerDef {
+        shape: LayerShape::Cloud,
+        count: 6,
+        factor: 0.5,
+        color: Color::rgba(1.0, 1.0, 1.0, 0.8),
+        min_size: 60.0,
+        max_size: 100.0,
+    },
+];
+const STRATOSPHERE_LAYERS: &[LayerDef] = &[
+    LayerDef {
+        shape: LayerShape::Star,
+        count: 80,
+        factor: 0.05,
+        color: Color::rgba(1.0, 1.0, 1.0, 0.3),
+        min_size: 2.0,
+        max_size: 3.0,
+    },
+    LayerDef {
+        shape: LayerShape::Cloud,
+        count: 5,
+        factor: 0.3,
+        color: Color::rgba(0.9, 0.9, 1.0, 0.3),
+        min_size: 80.0,
+        max_size: 140.0,
+    },
+];
+const ORBIT_LAYERS: &[LayerDef] = &[
+    LayerDef {
+        shape: LayerShape::Star,
+        count: 150,
+        factor: 0.05,
+        color: Color::rgba(1.0, 1.0, 1.0, 0.8),
+        min_size: 2.0,
+        max_size: 3.0,
+    },
+    LayerDef {
+        shape: LayerShape::Star,
+        count: 60,
+        factor: 0.15,
+        color: Color::rgb(1.0, 1.0, 1.0),
+        min_size: 3.0,
+        max_size: 4.0,
+    },
+];
+const DEEP_SPACE_LAYERS: &[LayerDef] = &[
+    LayerDef {
+        shape: LayerShape::Star,
+        count: 200,
+        factor: 0.02,
+        color: Color::rgba(0.8, 0.8, 1.0, 0.6),
+        min_size: 1.0,
+        max_size: 2.0,
+    },
+    LayerDef {
+        shape: LayerShape::Star,
+        count: 100,
+        factor: 0.1,
+        color: Color::rgb(1.0, 1.0, 1.0),
+        min_size: 2.0,
+        max_size: 3.0,
+    },
+    LayerDef {
+        shape: LayerShape::Star,
+        count: 30,
+        factor: 0.25,
+        color: Color::rgb(1.0, 0.9, 0.7),
+        min_size: 3.0,
+        max_size: 5.0,
+    },
+];
+/// parallax layers drawn while flying through `biome`, furthest first
+pub fn biome_layers(biome: Biome) -> &'static [LayerDef] {
+    match biome {
+        Biome::LowerAtmosphere => LOWER_ATMOSPHERE_LAYERS,
+        Biome::Stratosphere => STRATOSPHERE_LAYERS,
+        Biome::Orbit => ORBIT_LAYERS,
+        Biome::DeepSpace => DEEP_SPACE_LAYERS,
+    }
+}
+/// Something drawn behind the world that scrolls slower than the camera
+#[derive(Component)]
+pub struct Parallax {
+    /// position the element would have with the camera at the origin
+    pub base: Vec2,
+    /// 0.0 stays fixed to the screen, 1.0 moves with the world
+    pub factor: f32,
+    /// repeats vertically as well as horizontally
+    pub wrap_y: bool,
+}
+/// Parent of all elements of a layer, every element shares one material so
+/// the whole layer fades at once
+#[derive(Component)]
+struct BackgroundLayer {
+    biome: Biome,
+    material: Handle<ColorMaterial>,
+    base_alpha: f32,
+    opacity: f32,
+}
+/// Meshes of every layer shape and one material per layer, built once and
+/// shared by every run
+struct BackgroundAssets {
+    square: Handle<Mesh>,
+    circle: Handle<Mesh>,
+    triangle: Handle<Mesh>,
+    /// one per layer, in the order of [`Biome::ALL`] and then
+    /// [`biome_layers`]
+    materials: Vec<Handle<ColorMaterial>>,
+}
+impl FromWorld for BackgroundAssets {
+    fn from_world(world: &mut World) -> Self {
+        let mut meshes = world.resource_mut::<Assets<Mesh>>();
+        let square = meshes.add(shape::Quad::new(Vec2::ONE).into());
+        let circle = meshes.add(shape::Circle::new(0.5).into());
+        let triangle = meshes.add(shape::RegularPolygon::new(0.5, 3).into());
+        let mut materials = world.resource_mut::<Assets<ColorMaterial>>();
+        let materials = Biome::ALL
+            .iter()
+            .flat_map(|biome| biome_layers(*biome))
+            .map(|layer| materials.add(ColorMaterial::from(layer.color)))
+            .collect();
+        Self {
+            square,
+            circle,
+            triangle,
+            materials,
+        }
+    }
+}
+fn spawn_layers(
+    mut commands: Commands,
+    mut global_rng: ResMut<GlobalRng>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+    assets: Res<BackgroundAssets>,
+) {
+    let layer_count = assets.materials.len();
+    let mut depth = 0;
+    for biome in Biome::ALL {
+        for layer in biome_layers(biome) {
+            let z = BACKGROUND_Z + 0.08 * depth as f32 / layer_count as f32;
+            let material = assets.materials[depth].clone();
+            depth += 1;
+            let base_alpha = layer.color.a();
+            // the last run may have left the layer faded in
+            if let Some(material) = materials.get_mut(&material) {
+                material.color.set_a(0.0);
+            }
+            let mut elements = Vec::with_capacity(layer.count);
+            for _ in 0..layer.count {
+                let size = layer.min_size
+                    + (layer.max_size - layer.min_size) * global_rng.f32();
+                let x = 0.5 * TILE_SIZE.x * global_rng.f32_normalized();
+                let y = 0.5 * TILE_SIZE.y * global_rng.f32_normalized();
+                let (mesh, base, wrap_y, parts) = match layer.shape {
+                    LayerShape::Star => {
+                        (assets.square.clone(), Vec2::new(x, y), true, 1)
+                    }
+                    LayerShape::Cloud => {
+                        (assets.circle.clone(), Vec2::new(x, y), true, 3)
+                    }
+                    // mountains stand on the grass
+                    LayerShape::Mountain => (
+                        assets.triangle.clone(),
+                        Vec2::new(x, -350.0 + 0.25 * size),
+                        false,
+                        1,
+                    ),
+                };
+                for part in 0..parts {
+                    let offset = Vec2::new(
+                        (part as f32 - 0.5 * (parts - 1) as f32) * 0.6 * size,
+                        0.2 * size * global_rng.f32_normalized(),
+                    );
+                    let mut transform =
+                        Transform::from_translation((base + offset).extend(z));
+                    transform.scale = Vec3::new(size, size, 1.0);
+                    let element = commands
+                        .spawn_bundle(MaterialMesh2dBundle {
+                            mesh: mesh.clone().into(),
+                            material: material.clone(),
+                            transform,
+                            ..default()
+                        })
+                        .insert(Parallax {
+                            base: base + offset,
+                            factor: layer.factor,
+                            wrap_y,
+                        })
+                        .id();
+                    elements.push(element);
+                }
+            }
+            commands
+                .spawn_bundle(SpatialBundle {
+                    visibility: Visibility { is_visible: false },
+                    ..default()
+                })
+                .insert(BackgroundLayer {
+                    biome,
+                    material,
+                    base_alpha,
+                    opacity: 0.0,
+                })
+                .insert(GameEntity)
+                .push_children(&elements);
+        }
+    }
+}
+/// Places parallax elements relative to the camera, wrapping them around a
+/// tile centered on the camera so the background never runs out
+#[allow(clippy::type_complexity)]
+fn scroll_parallax(
+    mut queries: ParamSet<(
+        Query<&Transform, With<PlayerCamera>>,
+        Query<(&mut Transform, &Parallax), ()>,
+    )>,
+) {
+    let camera = match queries.p0().iter().next() {
+        Some(transform) => transform.translation.truncate(),
+        None => return,
+    };
+    for (mut transform, parallax) in queries.p1().iter_mut() {
+        let offset = parallax.base - camera * parallax.factor;
+        transform.translation.x = camera.x
+            + (offset.x + 0.5 * TILE_SIZE.x).rem_euclid(TILE_SIZE.x)
+            - 0.5 * TILE_SIZE.x;
+        transform.translation.y = if parallax.wrap_y {
+            camera.y + (offset.y + 0.5 * TILE_SIZE.y).rem_euclid(TILE_SIZE.y)
+                - 0.5 * TILE_SIZE.y
+        } else {
+            camera.y + offset.y
+        };
+    }
+}
+/// crossfades between the layers of the biome being left and entered
+fn fade_layers(
+    current: Res<CurrentBiome>,
+    mut layer_query: Query<(&mut BackgroundLayer, &mut Visibility), ()>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+    time: Res<Time>,
+) {
+    const FADE_SPEED: f32 = 0.5;
+    let step = FADE_SPEED * time.delta_seconds();
+    for (mut layer, mut visibility) in layer_query.iter_mut() {
+        let target = if layer.biome == current.biome {
+            1.0
+        } else {
+            0.0
+        };
+        if layer.opacity == target {
+            continue;
+        }
+        layer.opacity += (target - layer.opacity).clamp(-step, step);
+        visibility.is_visible = layer.opacity > 0.0;
+        if let Some(material) = materials.get_mut(&layer.material) {
+            material.color.set_a(layer.base_alpha * layer.opacity);
+        }
+    }
+}
@@ -0,0 +1,180 @@
+use super::{lerp_color, FlightDynamics, GameState, PlayerLabel};
+use bevy::prelude::*;
+use bevy_rapier2d::prelude::*;
+
+pub struct BiomePlugin;
+impl Plugin for BiomePlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(CurrentBiome::default())
+            .add_event::<BiomeChanged>()
+            .add_system_set(
+                SystemSet::on_enter(GameState::Game).with_system(reset_biome),
+            )
+            .add_system_set(
+                SystemSet::on_update(GameState::Game)
+                    .with_system(update_biome.label(BiomeLabel))
+                    .with_system(apply_biome_drag.after(BiomeLabel))
+                    .with_system(update_background_color.after(BiomeLabel)),
+            )
+            .add_system_set(
+                SystemSet::on_exit(GameState::Game)
+                    .with_system(reset_background_color),
+            );
+    }
+}
+/// Systems reading [`CurrentBiome`] run after this
+#[derive(SystemLabel, Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct BiomeLabel;
+/// Bands of altitude the climb passes through
+#[derive(Clone, Copy, Eq, PartialEq, Debug)]
+pub enum Biome {
+    LowerAtmosphere,
+    Stratosphere,
+    Orbit,
+    DeepSpace,
+}
+impl Biome {
+    pub const ALL: [Biome; 4] = [
+        Biome::LowerAtmosphere,
+        Biome::Stratosphere,
+        Biome::Orbit,
+        Biome::DeepSpace,
+    ];
+    /// altitude in pixels the biome starts at
+    pub fn start_altitude(&self) -> f32 {
+        match self {
+            Biome::LowerAtmosphere => f32::NEG_INFINITY,
+            Biome::Stratosphere => 5000.0,
+            Biome::Orbit => 15000.0,
+            Biome::DeepSpace => 30000.0,
+        }
+    }
+    pub fn from_altitude(altitude: f32) -> Self {
+        Self::ALL
+            .iter()
+            .rev()
+            .find(|biome| altitude >= biome.start_altitude())
+            .copied()
+            .unwrap_or(Biome::LowerAtmosphere)
+    }
+    /// the biome above this one
+    pub fn next(&self) -> Option<Self> {
+        match self {
+            Biome::LowerAtmosphere => Some(Biome::Stratosphere),
+            Biome::Stratosphere => Some(Biome::Orbit),
+            Biome::Orbit => Some(Biome::DeepSpace),
+            Biome::DeepSpace => None,
+        }
+    }
+    pub fn name(&self) -> &'static str {
+        match self {
+            Biome::LowerAtmosphere => "LOWER ATMOSPHERE",
+            Biome::Stratosphere => "STRATOSPHERE",
+            Biome::Orbit => "ORBIT",
+            Biome::DeepSpace => "DEEP SPACE",
+        }
+    }
+    /// background color at the bottom and the top of the biome
+    pub fn gradient(&self) -> (Color, Color) {
+        match self {
+            Biome::LowerAtmosphere => {
+                (Color::rgb(0.45, 0.7, 0.95), Color::rgb(0.3, 0.5, 0.85))
+            }
+            Biome::Stratosphere => {
+                (Color::rgb(0.3, 0.5, 0.85), Color::rgb(0.1, 0.15, 0.4))
+            }
+            Biome::Orbit => {
+                (Color::rgb(0.1, 0.15, 0.4), Color::rgb(0.02, 0.02, 0.1))
+            }
+            Biome::DeepSpace => {
+                (Color::rgb(0.02, 0.02, 0.1), Color::rgb(0.0, 0.0, 0.0))
+            }
+        }
+    }
+    /// Multiplier for how fast cooling runs out. Thinner air carries heat
+    /// away faster so the ship lasts longer between pickups.
+    pub fn cooling_drain(&self) -> f32 {
+        match self {
+            Biome::LowerAtmosphere => 1.0,
+            Biome::Stratosphere => 0.85,
+            Biome::Orbit => 0.7,
+            Biome::DeepSpace => 0.6,
+        }
+    }
+    /// linear damping applied to the ship, space has no drag at all
+    pub fn drag(&self) -> f32 {
+        match self {
+            Biome::LowerAtmosphere => 0.3,
+            Biome::Stratosphere => 0.15,
+            Biome::Orbit => 0.05,
+            Biome::DeepSpace => 0.0,
+        }
+    }
+    /// background color at an altitude inside the biome
+    pub fn background_color(&self, altitude: f32) -> Color {
+        let (bottom, top) = self.gradient();
+        let start = self.start_altitude().max(0.0);
+        let t = match self.next() {
+            Some(next) => ((altitude - start)
+                / (next.start_altitude() - start))
+                .clamp(0.0, 1.0),
+            None => 1.0,
+        };
+        lerp_color(bottom, top, t)
+    }
+}
+/// The biome the player is currently flying through
+pub struct CurrentBiome {
+    pub biome: Biome,
+    /// altitude of the player used to pick the biome
+    pub altitude: f32,
+}
+impl Default for CurrentBiome {
+    fn default() -> Self {
+        Self {
+            biome: Biome::LowerAtmosphere,
+            altitude: 0.0,
+        }
+    }
+}
+/// Sent when the player crosses into a different biome
+pub struct BiomeChanged(pub Biome);
+fn reset_biome(mut current: ResMut<CurrentBiome>) {
+    *current = CurrentBiome::default();
+}
+fn update_biome(
+    player_query: Query<&Transform, With<PlayerLabel>>,
+    mut current: ResMut<CurrentBiome>,
+    mut changed: EventWriter<BiomeChanged>,
+) {
+    let altitude = match player_query.iter().next() {
+        Some(transform) => transform.translation.y,
+        None => return,
+    };
+    current.altitude = altitude;
+    let biome = Biome::from_altitude(altitude);
+    if biome != current.biome {
+        current.biome = biome;
+        changed.send(BiomeChanged(biome));
+    }
+}
+fn apply_biome_drag(
+    mut player_query: Query<(&mut Damping, &FlightDynamics), With<PlayerLabel>>,
+    current: Res<CurrentBiome>,
+) {
+    for (mut damping, dynamics) in player_query.iter_mut() {
+        let drag = dynamics.linear_damping + current.biome.drag();
+        if damping.linear_damping != drag {
+            damping.linear_damping = drag;
+        }
+    }
+}
+fn update_background_color(
+    current: Res<CurrentBiome>,
+    mut clear_color: ResMut<ClearColor>,
+) {
+    clear_color.0 = current.biome.background_color(current.altitude);
+}
+fn reset_background_color(mut clear_color: ResMut<ClearColor>) {
+    clear_color.0 = Color::rgb(0.0, 0.0, 0.0);
+}
@@ -0,0 +1,173 @@
+use super::{FixedPhysicsStage, GameEntity, GameLabel, GameState, PlayerLabel};
+use bevy::prelude::*;
+use bevy_rapier2d::prelude::*;
+
+pub struct BoundsPlugin;
+impl Plugin for BoundsPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(WorldEdge::Walls)
+            .add_event::<PlayerWrapped>()
+            .add_system_set(
+                SystemSet::on_enter(GameState::Game).with_system(spawn_walls),
+            )
+            .add_system_set(
+                SystemSet::on_update(GameState::Game)
+                    .with_system(follow_walls)
+                    .with_system(wrap_player),
+            )
+            .stage(FixedPhysicsStage::Step, |schedule: &mut Schedule| {
+                schedule.add_system_set_to_stage(
+                    FixedPhysicsStage::Flight,
+                    SystemSet::on_update(GameState::Game).with_system(
+                        push_player_to_center.after(GameLabel::Thrust),
+                    ),
+                )
+            });
+    }
+}
+/// Half the width of the playable column, pickups spawn inside it
+pub const WORLD_HALF_WIDTH: f32 = 500.0;
+/// What happens when the ship reaches the side of the world
+#[derive(Clone, Copy, Eq, PartialEq, Debug)]
+pub enum WorldEdge {
+    /// solid walls the ship bounces off
+    Walls,
+    /// leaving one side teleports the ship to the other keeping velocity
+    Wrap,
+    /// a force that grows the further the ship strays past the edge
+    SoftForce,
+}
+impl WorldEdge {
+    const WALL_HALF_THICKNESS: f32 = 20.0;
+    const WALL_HALF_HEIGHT: f32 = 2000.0;
+    const WALL_COLOR: Color = Color::rgb(0.3, 0.3, 0.35);
+    /// distance from the edge at which the soft force starts
+    const SOFT_MARGIN: f32 = 100.0;
+    /// acceleration in pixels per second squared for every pixel past the
+    /// soft margin
+    const SOFT_STRENGTH: f32 = 10.0;
+    /// keeps pickups from spawning inside the walls
+    const PICKUP_MARGIN: f32 = 50.0;
+    pub fn name(&self) -> &'static str {
+        match self {
+            WorldEdge::Walls => "WALLS",
+            WorldEdge::Wrap => "WRAP",
+            WorldEdge::SoftForce => "SOFT",
+        }
+    }
+    pub fn next(&self) -> Self {
+        match self {
+            WorldEdge::Walls => WorldEdge::Wrap,
+            WorldEdge::Wrap => WorldEdge::SoftForce,
+            WorldEdge::SoftForce => WorldEdge::Walls,
+        }
+    }
+    /// largest distance from the center a pickup may spawn at
+    pub fn spawn_half_width(&self) -> f32 {
+        match self {
+            WorldEdge::Walls => WORLD_HALF_WIDTH - Self::PICKUP_MARGIN,
+            WorldEdge::Wrap | WorldEdge::SoftForce => WORLD_HALF_WIDTH,
+        }
+    }
+    /// Applies the edge rule to something that has left the world sideways,
+    /// returns false if it should be removed instead
+    pub fn contain(&self, translation: &mut Vec3) -> bool {
+        match self {
+            WorldEdge::Wrap => {
+                translation.x = wrap(translation.x);
+                true
+            }
+            WorldEdge::Walls | WorldEdge::SoftForce => {
+                translation.x.abs() <= WORLD_HALF_WIDTH
+            }
+        }
+    }
+}
+/// wraps a horizontal position back into the world
+fn wrap(x: f32) -> f32 {
+    (x + WORLD_HALF_WIDTH).rem_euclid(2.0 * WORLD_HALF_WIDTH) - WORLD_HALF_WIDTH
+}
+/// Sent when the player wraps around, contains the horizontal offset applied
+pub struct PlayerWrapped(pub f32);
+#[derive(Component)]
+struct WallTag;
+fn spawn_walls(mut commands: Commands, edge: Res<WorldEdge>) {
+    if *edge != WorldEdge::Walls {
+        return;
+    }
+    for side in [-1.0, 1.0] {
+        let x = side * (WORLD_HALF_WIDTH + WorldEdge::WALL_HALF_THICKNESS);
+        commands
+            .spawn_bundle(SpriteBundle {
+                sprite: Sprite {
+                    color: WorldEdge::WALL_COLOR,
+                    custom_size: Some(Vec2::new(
+                        2.0 * WorldEdge::WALL_HALF_THICKNESS,
+                        2.0 * WorldEdge::WALL_HALF_HEIGHT,
+                    )),
+                    ..default()
+                },
+                transform: Transform::from_translation(Vec3::new(x, 0.0, 0.0)),
+                ..default()
+            })
+            .insert(Collider::cuboid(
+                WorldEdge::WALL_HALF_THICKNESS,
+                WorldEdge::WALL_HALF_HEIGHT,
+            ))
+            .insert(WallTag)
+            .insert(GameEntity);
+    }
+}
+/// keeps the walls centered on the player so they never run out
+#[allow(clippy::type_complexity)]
+fn follow_walls(
+    mut queries: ParamSet<(
+        Query<&Transform, With<PlayerLabel>>,
+        Query<&mut Transform, With<WallTag>>,
+    )>,
+) {
+    let player_y = match queries.p0().iter().next() {
+        Some(transform) => transform.translation.y,
+        None => return,
+    };
+    for mut wall in queries.p1().iter_mut() {
+        wall.translation.y = player_y;
+    }
+}
+fn wrap_player(
+    mut player_query: Query<&mut Transform, With<PlayerLabel>>,
+    edge: Res<WorldEdge>,
+    mut wrapped: EventWriter<PlayerWrapped>,
+) {
+    if *edge != WorldEdge::Wrap {
+        return;
+    }
+    for mut transform in player_query.iter_mut() {
+        let x = transform.translation.x;
+        if x.abs() > WORLD_HALF_WIDTH {
+            transform.translation.x = wrap(x);
+            wrapped.send(PlayerWrapped(transform.translation.x - x));
+        }
+    }
+}
+/// adds the soft edge push on top of the thrust of the step
+fn push_player_to_center(
+    mut player_query: Query<
+        (&Transform, &mut ExternalForce, &ReadMassProperties),
+        With<PlayerLabel>,
+    >,
+    edge: Res<WorldEdge>,
+) {
+    if *edge != WorldEdge::SoftForce {
+        return;
+    }
+    let start = WORLD_HALF_WIDTH - WorldEdge::SOFT_MARGIN;
+    for (transform, mut force, mass) in player_query.iter_mut() {
+        let x = transform.translation.x;
+        let overshoot = x.abs() - start;
+        if overshoot > 0.0 {
+            let accel = -x.signum() * overshoot * WorldEdge::SOFT_STRENGTH;
+            force.force.x += accel * mass.0.mass;
+        }
+    }
+}
@@ -0,0 +1,228 @@
+use super::{
+    ActiveModifiers, Checkpoints, GameEntity, GameLabel, GameState,
+    PlayerCoolingTimer, PlayerLabel, PlayerWrapped,
+};
+use bevy::prelude::*;
+use bevy_rapier2d::prelude::*;
+use bevy_turborand::*;
+
+pub struct CameraPlugin;
+impl Plugin for CameraPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(CameraSettings::default())
+            .add_event::<CameraShake>()
+            .add_system_set(
+                SystemSet::on_enter(GameState::Game)
+                    .with_system(spawn_camera.after(GameLabel::Checkpoint)),
+            )
+            .add_system_set(
+                SystemSet::on_update(GameState::Game)
+                    .with_system(shake_on_impact)
+                    .with_system(shake_on_overheat)
+                    .with_system(follow_player.label(CameraLabel::Follow))
+                    .with_system(
+                        apply_shake
+                            .label(CameraLabel::Shake)
+                            .after(CameraLabel::Follow),
+                    )
+                    .with_system(update_camera_zoom),
+            );
+    }
+}
+/// Systems that need the final camera position run after [`CameraLabel::Shake`]
+#[derive(SystemLabel, Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum CameraLabel {
+    Follow,
+    Shake,
+}
+#[derive(Component)]
+pub struct PlayerCamera;
+/// Tuning for the player camera
+pub struct CameraSettings {
+    /// how fast the camera catches up with its target, higher is snappier
+    pub follow_stiffness: f32,
+    /// seconds of player velocity the camera leads the ship by
+    pub look_ahead_time: f32,
+    /// largest look ahead offset in pixels
+    pub max_look_ahead: f32,
+    /// keeps the camera from ever moving down, like the original camera
+    pub never_descend: bool,
+    /// largest shake offset in pixels at full trauma
+    pub shake_strength: f32,
+    /// trauma lost per second
+    pub shake_decay: f32,
+    /// trauma added per pixel per second of impact speed
+    pub impact_shake: f32,
+    /// cooling fraction used after which the camera starts shaking
+    pub overheat_threshold: f32,
+    /// trauma per second added while overheating
+    pub overheat_shake: f32,
+    /// zooms out while flying fast
+    pub dynamic_zoom: bool,
+    /// speed in pixels per second at which zooming out starts
+    pub zoom_start_speed: f32,
+    /// speed in pixels per second at which the camera is zoomed out fully
+    pub zoom_full_speed: f32,
+    /// orthographic scale when zoomed out fully
+    pub max_zoom: f32,
+    /// how fast the zoom approaches its target, higher is snappier
+    pub zoom_stiffness: f32,
+}
+impl Default for CameraSettings {
+    fn default() -> Self {
+        Self {
+            follow_stiffness: 6.0,
+            look_ahead_time: 0.25,
+            max_look_ahead: 200.0,
+            never_descend: true,
+            shake_strength: 20.0,
+            shake_decay: 1.5,
+            impact_shake: 0.001,
+            overheat_threshold: 0.8,
+            overheat_shake: 1.0,
+            dynamic_zoom: true,
+            zoom_start_speed: 600.0,
+            zoom_full_speed: 2000.0,
+            max_zoom: 1.5,
+            zoom_stiffness: 2.0,
+        }
+    }
+}
+/// Adds trauma to the camera, trauma is clamped to 1.0
+pub struct CameraShake(pub f32);
+/// State of the camera controller. The shake offset is applied on top of
+/// `focus` so the damped follow never sees it.
+#[derive(Component, Default)]
+pub struct CameraRig {
+    focus: Vec2,
+    trauma: f32,
+}
+fn spawn_camera(mut commands: Commands, checkpoints: Res<Checkpoints>) {
+    // start over the ship instead of panning up to a continued run
+    let focus = checkpoints.spawn_position();
+    commands
+        .spawn_bundle(Camera2dBundle {
+            transform: Transform::from_translation(focus.extend(999.9)),
+            ..default()
+        })
+        .insert(CameraRig { focus, ..default() })
+        .insert(GameEntity)
+        .insert(PlayerCamera);
+}
+/// fraction of the remaining distance covered this frame, independent of
+/// frame rate
+fn damp_factor(stiffness: f32, delta_seconds: f32) -> f32 {
+    1.0 - (-stiffness * delta_seconds).exp()
+}
+fn follow_player(
+    player_query: Query<(&Transform, &Velocity), With<PlayerLabel>>,
+    mut camera_query: Query<&mut CameraRig, With<PlayerCamera>>,
+    mut wrapped_events: EventReader<PlayerWrapped>,
+    settings: Res<CameraSettings>,
+    time: Res<Time>,
+) {
+    // jump along with a wrapping player instead of panning across the world
+    let wrap_offset: f32 = wrapped_events.iter().map(|wrap| wrap.0).sum();
+    let player = player_query.iter().next();
+    if player.is_none() {
+        error!("player not found");
+        return;
+    }
+    let (transform, velocity) = player.unwrap();
+    let look_ahead = (velocity.linvel * settings.look_ahead_time)
+        .clamp_length_max(settings.max_look_ahead);
+    let mut target = transform.translation.truncate() + look_ahead;
+    let t = damp_factor(settings.follow_stiffness, time.delta_seconds());
+    for mut rig in camera_query.iter_mut() {
+        rig.focus.x += wrap_offset;
+        if settings.never_descend {
+            target.y = target.y.max(rig.focus.y);
+        }
+        rig.focus = rig.focus.lerp(target, t);
+    }
+}
+fn apply_shake(
+    mut camera_query: Query<(&mut Transform, &mut CameraRig), ()>,
+    mut shake_events: EventReader<CameraShake>,
+    settings: Res<CameraSettings>,
+    mut global_rng: ResMut<GlobalRng>,
+    time: Res<Time>,
+) {
+    let added: f32 = shake_events.iter().map(|shake| shake.0).sum();
+    for (mut transform, mut rig) in camera_query.iter_mut() {
+        rig.trauma = (rig.trauma + added).min(1.0);
+        rig.trauma =
+            (rig.trauma - settings.shake_decay * time.delta_seconds()).max(0.0);
+        // squaring keeps small amounts of trauma subtle
+        let strength = settings.shake_strength * rig.trauma * rig.trauma;
+        let offset =
+            Vec2::new(global_rng.f32_normalized(), global_rng.f32_normalized())
+                * strength;
+        transform.translation.x = rig.focus.x + offset.x;
+        transform.translation.y = rig.focus.y + offset.y;
+    }
+}
+fn shake_on_impact(
+    mut collision_events: EventReader<CollisionEvent>,
+    player_query: Query<(Entity, &Velocity), With<PlayerLabel>>,
+    sensor_query: Query<(), With<Sensor>>,
+    settings: Res<CameraSettings>,
+    mut shake: EventWriter<CameraShake>,
+) {
+    for collision in collision_events.iter() {
+        if let CollisionEvent::Started(e1, e2, _) = collision {
+            if sensor_query.contains(*e1) || sensor_query.contains(*e2) {
+                continue;
+            }
+            for (player, velocity) in player_query.iter() {
+                if player == *e1 || player == *e2 {
+                    shake.send(CameraShake(
+                        velocity.linvel.length() * settings.impact_shake,
+                    ));
+                }
+            }
+        }
+    }
+}
+fn shake_on_overheat(
+    player_query: Query<&PlayerCoolingTimer, With<PlayerLabel>>,
+    settings: Res<CameraSettings>,
+    time: Res<Time>,
+    mut shake: EventWriter<CameraShake>,
+) {
+    for cooling in player_query.iter() {
+        let used = cooling.get_frac_used();
+        if used > settings.overheat_threshold {
+            let severity = (used - settings.overheat_threshold)
+                / (1.0 - settings.overheat_threshold);
+            shake.send(CameraShake(
+                severity * settings.overheat_shake * time.delta_seconds(),
+            ));
+        }
+    }
+}
+fn update_camera_zoom(
+    player_query: Query<&Velocity, With<PlayerLabel>>,
+    mut camera_query: Query<&mut OrthographicProjection, With<PlayerCamera>>,
+    settings: Res<CameraSettings>,
+    modifiers: Res<ActiveModifiers>,
+    time: Res<Time>,
+) {
+    let speed = player_query
+        .iter()
+        .next()
+        .map(|velocity| velocity.linvel.length())
+        .unwrap_or(0.0);
+    let mut target = 1.0;
+    if settings.dynamic_zoom {
+        let t = ((speed - settings.zoom_start_speed)
+            / (settings.zoom_full_speed - settings.zoom_start_speed))
+            .clamp(0.0, 1.0);
+        target += (settings.max_zoom - 1.0) * t;
+    }
+    target *= modifiers.camera_scale();
+    let t = damp_factor(settings.zoom_stiffness, time.delta_seconds());
+    for mut projection in camera_query.iter_mut() {
+        projection.scale += (target - projection.scale) * t;
+    }
+}
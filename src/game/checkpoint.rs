@@ -0,0 +1,193 @@
+use super::{
+    GameLabel, GameState, LevelObstacle, PlayerFuel, PlayerLabel, PlayerScore,
+    RunSeed,
+};
+use bevy::prelude::*;
+use bevy_rapier2d::prelude::*;
+
+pub struct CheckpointPlugin;
+impl Plugin for CheckpointPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(Checkpoints::default())
+            .add_event::<CheckpointReached>()
+            .add_system_set(
+                SystemSet::on_enter(GameState::Game)
+                    .with_system(begin_run.label(GameLabel::Checkpoint)),
+            )
+            .add_system_set(
+                SystemSet::on_update(GameState::Game)
+                    .with_system(reach_checkpoint),
+            );
+    }
+}
+/// Altitude between two checkpoint platforms
+pub const CHECKPOINT_INTERVAL: f32 = 5000.0;
+/// Everything needed to pick a run back up at a checkpoint. The level is
+/// rebuilt from the run seed, so only the ship has to be saved.
+#[derive(Clone)]
+pub struct CheckpointSnapshot {
+    /// altitude of the platform the checkpoint was saved on
+    pub altitude: f32,
+    pub fuel: PlayerFuel,
+    pub score: PlayerScore,
+    pub seed: u64,
+}
+/// Checkpoints reached during the current run
+#[derive(Default)]
+pub struct Checkpoints {
+    last: Option<CheckpointSnapshot>,
+    /// set by continuing, the next run starts from `last`
+    resume: bool,
+    /// the checkpoint the current run was started from
+    resumed: Option<CheckpointSnapshot>,
+    /// highest altitude of this chain of continued runs that is already
+    /// counted in the profile totals
+    recorded_altitude: f32,
+}
+impl Checkpoints {
+    /// score taken off for every continue
+    pub const CONTINUE_PENALTY: u32 = 25;
+    /// how far above the platform the ship is put back
+    const RESPAWN_HEIGHT: f32 = 100.0;
+    /// altitude of the highest checkpoint reached
+    pub fn last_altitude(&self) -> Option<f32> {
+        self.last.as_ref().map(|snapshot| snapshot.altitude)
+    }
+    pub fn can_continue(&self) -> bool {
+        self.last.is_some()
+    }
+    /// starts the next run from the last checkpoint instead of the ground
+    pub fn resume(&mut self) {
+        self.resume = self.can_continue();
+    }
+    /// the checkpoint the current run was started from, if any
+    pub fn resumed(&self) -> Option<&CheckpointSnapshot> {
+        self.resumed.as_ref()
+    }
+    /// where the ship starts, on the ground or over the resumed checkpoint
+    pub fn spawn_position(&self) -> Vec2 {
+        match &self.resumed {
+            Some(snapshot) => {
+                Vec2::new(0.0, snapshot.altitude + Self::RESPAWN_HEIGHT)
+            }
+            None => Vec2::ZERO,
+        }
+    }
+    /// counts a finished run reaching `altitude`, returns how much of it
+    /// has not been counted by the runs it was continued from
+    pub fn record_altitude(&mut self, altitude: f32) -> f32 {
+        let gained = (altitude - self.recorded_altitude).max(0.0);
+        self.recorded_altitude = self.recorded_altitude.max(altitude);
+        gained
+    }
+    /// score to start the run with, less the penalty for continuing
+    pub fn starting_score(&self) -> PlayerScore {
+        match &self.resumed {
+            Some(snapshot) => {
+                let mut score = snapshot.score.clone();
+                score.penalize(Self::CONTINUE_PENALTY);
+                score
+            }
+            None => PlayerScore::new(),
+        }
+    }
+}
+/// Sent when the ship touches a checkpoint platform above the last one
+pub struct CheckpointReached {
+    pub altitude: f32,
+}
+/// Landing platform that saves a checkpoint when touched
+#[derive(Component)]
+pub struct CheckpointPlatform {
+    pub altitude: f32,
+}
+impl CheckpointPlatform {
+    pub const SIZE: Vec2 = Vec2::new(300.0, 20.0);
+}
+mod colors {
+    use bevy::prelude::*;
+
+    pub const PLATFORM_COLOR: Color = Color::rgb(0.2, 0.6, 0.3);
+    pub const PLATFORM_REACHED_COLOR: Color = Color::rgb(0.4, 1.0, 0.5);
+}
+/// spawns a checkpoint platform centered at `center` as a child of a level
+/// chunk, `altitude` is the world height of its center
+pub fn spawn_checkpoint_platform(
+    parent: &mut ChildBuilder,
+    center: Vec2,
+    altitude: f32,
+    reached: bool,
+) {
+    let size = CheckpointPlatform::SIZE;
+    parent
+        .spawn_bundle(SpriteBundle {
+            sprite: Sprite {
+                color: if reached {
+                    colors::PLATFORM_REACHED_COLOR
+                } else {
+                    colors::PLATFORM_COLOR
+                },
+                custom_size: Some(size),
+                ..default()
+            },
+            transform: Transform::from_translation(center.extend(0.0)),
+            ..default()
+        })
+        .insert(Collider::cuboid(0.5 * size.x, 0.5 * size.y))
+        .insert(CheckpointPlatform { altitude })
+        .insert(LevelObstacle);
+}
+/// forgets the checkpoints of the previous run unless it is being continued
+fn begin_run(mut checkpoints: ResMut<Checkpoints>) {
+    checkpoints.resumed = if checkpoints.resume {
+        checkpoints.last.clone()
+    } else {
+        checkpoints.last = None;
+        checkpoints.recorded_altitude = 0.0;
+        None
+    };
+    checkpoints.resume = false;
+}
+fn reach_checkpoint(
+    player_query: Query<(Entity, &PlayerFuel, &PlayerScore), With<PlayerLabel>>,
+    mut platform_query: Query<(&CheckpointPlatform, &mut Sprite)>,
+    mut collision_events: EventReader<CollisionEvent>,
+    mut checkpoints: ResMut<Checkpoints>,
+    mut reached_events: EventWriter<CheckpointReached>,
+    run_seed: Res<RunSeed>,
+) {
+    let (player, fuel, score) = match player_query.iter().next() {
+        Some(player) => player,
+        None => {
+            error!("player not found");
+            return;
+        }
+    };
+    for event in collision_events.iter() {
+        let platform = match event {
+            CollisionEvent::Started(e1, e2, _) if *e1 == player => *e2,
+            CollisionEvent::Started(e1, e2, _) if *e2 == player => *e1,
+            _ => continue,
+        };
+        let (platform, mut sprite) = match platform_query.get_mut(platform) {
+            Ok(platform) => platform,
+            Err(_) => continue,
+        };
+        if checkpoints
+            .last_altitude()
+            .is_some_and(|altitude| altitude >= platform.altitude)
+        {
+            continue;
+        }
+        sprite.color = colors::PLATFORM_REACHED_COLOR;
+        checkpoints.last = Some(CheckpointSnapshot {
+            altitude: platform.altitude,
+            fuel: fuel.clone(),
+            score: score.clone(),
+            seed: run_seed.0,
+        });
+        reached_events.send(CheckpointReached {
+            altitude: platform.altitude,
+        });
+    }
+}
@@ -0,0 +1,138 @@
+use super::{mix_seed, GameMode, GameState, PlayerLabel, PlayerScore, Ship};
+use crate::storage;
+use bevy::prelude::*;
+
+pub struct DailyPlugin;
+impl Plugin for DailyPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(DailyChallenge::load())
+            .add_system_set(
+                SystemSet::on_enter(GameState::MainMenu)
+                    .with_system(refresh_daily),
+            )
+            .add_system_set(
+                SystemSet::on_enter(GameState::Game)
+                    .with_system(start_daily_attempt),
+            )
+            .add_system_set(
+                SystemSet::on_update(GameState::Game)
+                    .with_system(update_daily_best),
+            )
+            .add_system_set(
+                SystemSet::on_exit(GameState::Game)
+                    .with_system(save_daily_best),
+            );
+    }
+}
+/// The challenge for the current UTC day. Every player gets the same seed
+/// on the same day and only the first run of the day counts.
+pub struct DailyChallenge {
+    day: u64,
+    attempted: bool,
+    best: PlayerScore,
+    /// ship the best score was set with
+    best_ship: Ship,
+}
+impl DailyChallenge {
+    const SAVE_KEY: &'static str = "daily_challenge";
+    pub fn new(day: u64) -> Self {
+        Self {
+            day,
+            attempted: false,
+            best: PlayerScore::new(),
+            best_ship: Ship::Standard,
+        }
+    }
+    /// loads today's challenge, discarding progress saved on an earlier day
+    pub fn load() -> Self {
+        let today = storage::utc_day();
+        let mut daily = Self::new(today);
+        if let Some(data) = storage::load(Self::SAVE_KEY) {
+            let fields = storage::parse_fields(&data);
+            let saved_day = fields.get("day").and_then(|d| d.parse().ok());
+            if saved_day == Some(today) {
+                daily.attempted = fields
+                    .get("attempted")
+                    .and_then(|a| a.parse().ok())
+                    .unwrap_or(false);
+                if let Some(best) =
+                    fields.get("best").and_then(|b| b.parse().ok())
+                {
+                    daily.best = PlayerScore::from_points(best);
+                }
+                if let Some(ship) =
+                    fields.get("ship").and_then(|s| Ship::from_name(s))
+                {
+                    daily.best_ship = ship;
+                }
+            }
+        }
+        daily
+    }
+    pub fn save(&self) {
+        storage::save(
+            Self::SAVE_KEY,
+            &storage::write_fields(&[
+                ("day", self.day.to_string()),
+                ("attempted", self.attempted.to_string()),
+                ("best", self.best.get_score().to_string()),
+                ("ship", self.best_ship.name().to_string()),
+            ]),
+        );
+    }
+    /// rng seed shared by everyone playing on the same UTC day
+    pub fn seed(&self) -> u64 {
+        mix_seed(self.day)
+    }
+    /// date of the challenge formatted as YYYY-MM-DD
+    pub fn date(&self) -> String {
+        let (year, month, day) = storage::civil_from_days(self.day);
+        format!("{:04}-{:02}-{:02}", year, month, day)
+    }
+    pub fn can_attempt(&self) -> bool {
+        !self.attempted
+    }
+    pub fn get_best(&self) -> &PlayerScore {
+        &self.best
+    }
+    pub fn get_best_ship(&self) -> Ship {
+        self.best_ship
+    }
+}
+fn refresh_daily(mut daily: ResMut<DailyChallenge>) {
+    if daily.day != storage::utc_day() {
+        *daily = DailyChallenge::load();
+    }
+}
+/// uses up the attempt as soon as the run starts so quitting early does not
+/// give a second try
+fn start_daily_attempt(
+    game_mode: Res<GameMode>,
+    mut daily: ResMut<DailyChallenge>,
+) {
+    if *game_mode == GameMode::DailyChallenge {
+        daily.attempted = true;
+        daily.save();
+    }
+}
+fn update_daily_best(
+    game_mode: Res<GameMode>,
+    player_query: Query<&PlayerScore, With<PlayerLabel>>,
+    mut daily: ResMut<DailyChallenge>,
+    ship: Res<Ship>,
+) {
+    if *game_mode != GameMode::DailyChallenge {
+        return;
+    }
+    for score in player_query.iter() {
+        if score.get_score() > daily.best.get_score() {
+            daily.best = score.clone();
+            daily.best_ship = *ship;
+        }
+    }
+}
+fn save_daily_best(game_mode: Res<GameMode>, daily: Res<DailyChallenge>) {
+    if *game_mode == GameMode::DailyChallenge {
+        daily.save();
+    }
+}
@@ -0,0 +1,361 @@
+use super::{
+    Biome, FixedPhysicsStage, GameEntity, GameLabel, GameState, Hull,
+    PlayerFuel, PlayerLabel, RunSeed, WorldEdge, FUEL_MAX_Y_DIST,
+};
+use bevy::{prelude::*, sprite::MaterialMesh2dBundle};
+use bevy_rapier2d::prelude::*;
+use bevy_turborand::*;
+
+pub struct DronePlugin;
+impl Plugin for DronePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<DroneAssets>()
+            .insert_resource(DronePush::default())
+            .add_system_set(
+                SystemSet::on_enter(GameState::Game).with_system(
+                    insert_drone_spawner.after(GameLabel::RunSeed),
+                ),
+            )
+            .add_system_set(
+                SystemSet::on_update(GameState::Game)
+                    .with_system(spawn_drones)
+                    .with_system(think_drones.before(move_drones))
+                    .with_system(move_drones)
+                    .with_system(drone_contact)
+                    .with_system(clean_up_drones),
+            )
+            .stage(FixedPhysicsStage::Step, |schedule: &mut Schedule| {
+                schedule.add_system_set_to_stage(
+                    FixedPhysicsStage::Flight,
+                    SystemSet::on_update(GameState::Game)
+                        .with_system(push_player),
+                )
+            });
+    }
+}
+/// What a drone does when it reaches the ship
+#[derive(Clone, Copy, Eq, PartialEq, Debug)]
+pub enum DroneKind {
+    /// drains fuel from the ship
+    Thief,
+    /// shoves the ship off course
+    Pusher,
+}
+impl DroneKind {
+    fn speed(&self) -> f32 {
+        match self {
+            DroneKind::Thief => 250.0,
+            DroneKind::Pusher => 320.0,
+        }
+    }
+}
+/// Behaviour of a drone
+#[derive(Component, Debug)]
+pub enum DroneState {
+    /// patrols back and forth around where it spawned
+    Idle,
+    /// flies straight at the ship
+    Pursue,
+    /// backs off after touching the ship until the timer finishes
+    Retreat(Timer),
+}
+#[derive(Component)]
+pub struct Drone {
+    pub kind: DroneKind,
+    /// x the drone patrols around while idle
+    home_x: f32,
+    /// direction of the patrol, 1.0 or -1.0
+    patrol: f32,
+}
+impl Drone {
+    const MAX_DRONES: usize = 3;
+    const RADIUS: f32 = 16.0;
+    /// distance at which an idle drone notices the ship
+    const DETECT_RADIUS: f32 = 450.0;
+    /// distance at which a pursuing drone gives up
+    const LOSE_RADIUS: f32 = 700.0;
+    const PATROL_WIDTH: f32 = 200.0;
+    const PATROL_SPEED: f32 = 80.0;
+    const RETREAT_TIME_SEC: f32 = 2.0;
+    /// fuel taken by a thief per touch
+    const STEAL_AMOUNT: f32 = 150.0;
+    /// velocity change a pusher gives the ship in pixels per second
+    const PUSH_SPEED: f32 = 600.0;
+}
+/// Velocity change from a pusher waiting for the next physics step, one
+/// push per frame however many pushers touch the ship
+#[derive(Default)]
+struct DronePush(Option<Vec2>);
+#[derive(Component)]
+struct DroneSpawner {
+    timer: Timer,
+}
+impl DroneSpawner {
+    const RESPAWN_TIME_SEC: f32 = 4.0;
+}
+/// salt for the [`RunSeed`] of drone placement
+const DRONE_SEED: u64 = 0x0044_524f_4e45;
+/// Mesh and material of each kind of drone, built once
+struct DroneAssets {
+    thief_mesh: Handle<Mesh>,
+    thief_material: Handle<ColorMaterial>,
+    pusher_mesh: Handle<Mesh>,
+    pusher_material: Handle<ColorMaterial>,
+}
+impl DroneAssets {
+    fn get(&self, kind: DroneKind) -> (Handle<Mesh>, Handle<ColorMaterial>) {
+        match kind {
+            DroneKind::Thief => {
+                (self.thief_mesh.clone(), self.thief_material.clone())
+            }
+            DroneKind::Pusher => {
+                (self.pusher_mesh.clone(), self.pusher_material.clone())
+            }
+        }
+    }
+}
+impl FromWorld for DroneAssets {
+    fn from_world(world: &mut World) -> Self {
+        let mut meshes = world.resource_mut::<Assets<Mesh>>();
+        let thief_mesh =
+            meshes.add(shape::RegularPolygon::new(Drone::RADIUS, 3).into());
+        let pusher_mesh =
+            meshes.add(shape::RegularPolygon::new(Drone::RADIUS, 6).into());
+        let mut materials = world.resource_mut::<Assets<ColorMaterial>>();
+        Self {
+            thief_mesh,
+            thief_material: materials
+                .add(ColorMaterial::from(colors::THIEF_COLOR)),
+            pusher_mesh,
+            pusher_material: materials
+                .add(ColorMaterial::from(colors::PUSHER_COLOR)),
+        }
+    }
+}
+mod colors {
+    use bevy::prelude::*;
+
+    pub const THIEF_COLOR: Color = Color::rgb(0.7, 0.9, 0.2);
+    pub const PUSHER_COLOR: Color = Color::rgb(0.9, 0.3, 0.6);
+}
+fn insert_drone_spawner(mut commands: Commands, run_seed: Res<RunSeed>) {
+    commands.insert_resource(DronePush::default());
+    commands
+        .spawn()
+        .insert(DroneSpawner {
+            timer: Timer::from_seconds(DroneSpawner::RESPAWN_TIME_SEC, true),
+        })
+        .insert(RngComponent::with_seed(run_seed.derive(DRONE_SEED)))
+        .insert(GameEntity);
+}
+fn spawn_drones(
+    mut commands: Commands,
+    mut spawner_query: Query<(&mut DroneSpawner, &mut RngComponent), ()>,
+    player_query: Query<&Transform, With<PlayerLabel>>,
+    drone_query: Query<(), With<Drone>>,
+    time: Res<Time>,
+    edge: Res<WorldEdge>,
+    assets: Res<DroneAssets>,
+) {
+    let player_y = match player_query.iter().next() {
+        Some(transform) => transform.translation.y,
+        None => {
+            error!("player not found");
+            return;
+        }
+    };
+    for (mut spawner, mut rng) in spawner_query.iter_mut() {
+        // drones only show up above the lower atmosphere
+        let biome = Biome::from_altitude(player_y);
+        if !spawner.timer.tick(time.delta()).just_finished()
+            || biome == Biome::LowerAtmosphere
+            || drone_query.iter().count() >= Drone::MAX_DRONES
+        {
+            continue;
+        }
+        // more pushers in deep space where there is no drag to recover
+        let pusher_chance = if biome == Biome::DeepSpace { 0.6 } else { 0.4 };
+        let kind = if rng.f32() < pusher_chance {
+            DroneKind::Pusher
+        } else {
+            DroneKind::Thief
+        };
+        let (mesh, material) = assets.get(kind);
+        let x = edge.spawn_half_width() * rng.f32_normalized();
+        let y = player_y + FUEL_MAX_Y_DIST * (0.5 + 0.4 * rng.f32());
+        commands
+            .spawn_bundle(MaterialMesh2dBundle {
+                mesh: mesh.into(),
+                material,
+                transform: Transform::from_xyz(x, y, 0.0),
+                ..default()
+            })
+            .insert(Collider::ball(Drone::RADIUS))
+            .insert(Sensor)
+            .insert(Drone {
+                kind,
+                home_x: x,
+                patrol: if rng.bool() { 1.0 } else { -1.0 },
+            })
+            .insert(DroneState::Idle)
+            .insert(GameEntity);
+    }
+}
+/// switches drones between idle, pursue and retreat
+fn think_drones(
+    player_query: Query<&Transform, With<PlayerLabel>>,
+    mut drone_query: Query<(&Transform, &mut DroneState), With<Drone>>,
+    time: Res<Time>,
+) {
+    let player_position = match player_query.iter().next() {
+        Some(transform) => transform.translation.truncate(),
+        None => {
+            error!("player not found");
+            return;
+        }
+    };
+    for (transform, mut state) in drone_query.iter_mut() {
+        let distance =
+            transform.translation.truncate().distance(player_position);
+        let next = match &mut *state {
+            DroneState::Idle if distance < Drone::DETECT_RADIUS => {
+                Some(DroneState::Pursue)
+            }
+            DroneState::Pursue if distance > Drone::LOSE_RADIUS => {
+                Some(DroneState::Idle)
+            }
+            DroneState::Retreat(timer) => timer
+                .tick(time.delta())
+                .finished()
+                .then_some(DroneState::Idle),
+            _ => None,
+        };
+        if let Some(next) = next {
+            *state = next;
+        }
+    }
+}
+fn move_drones(
+    player_query: Query<&Transform, With<PlayerLabel>>,
+    mut drone_query: Query<
+        (&mut Transform, &mut Drone, &DroneState),
+        Without<PlayerLabel>,
+    >,
+    time: Res<Time>,
+) {
+    let player_position = match player_query.iter().next() {
+        Some(transform) => transform.translation.truncate(),
+        None => {
+            error!("player not found");
+            return;
+        }
+    };
+    for (mut transform, mut drone, state) in drone_query.iter_mut() {
+        let to_player = (player_position - transform.translation.truncate())
+            .normalize_or_zero();
+        let velocity = match state {
+            DroneState::Idle => {
+                let offset = transform.translation.x - drone.home_x;
+                if offset * drone.patrol > Drone::PATROL_WIDTH {
+                    drone.patrol = -drone.patrol;
+                }
+                Vec2::new(drone.patrol * Drone::PATROL_SPEED, 0.0)
+            }
+            DroneState::Pursue => to_player * drone.kind.speed(),
+            DroneState::Retreat(_) => -to_player * drone.kind.speed(),
+        };
+        transform.translation += (velocity * time.delta_seconds()).extend(0.0);
+    }
+}
+fn drone_contact(
+    mut player_query: Query<
+        (Entity, &Transform, &mut PlayerFuel, &mut Hull),
+        With<PlayerLabel>,
+    >,
+    mut drone_query: Query<(&Transform, &Drone, &mut DroneState)>,
+    mut collision_events: EventReader<CollisionEvent>,
+    mut push: ResMut<DronePush>,
+) {
+    let (player, player_transform, mut fuel, mut hull) =
+        match player_query.iter_mut().next() {
+            Some(player) => player,
+            None => {
+                error!("player not found");
+                return;
+            }
+        };
+    for event in collision_events.iter() {
+        let drone_entity = match event {
+            CollisionEvent::Started(e1, e2, _) if *e1 == player => *e2,
+            CollisionEvent::Started(e1, e2, _) if *e2 == player => *e1,
+            _ => continue,
+        };
+        let (transform, drone, mut state) =
+            match drone_query.get_mut(drone_entity) {
+                Ok(drone) => drone,
+                Err(_) => continue,
+            };
+        if matches!(*state, DroneState::Retreat(_)) {
+            continue;
+        }
+        *state = DroneState::Retreat(Timer::from_seconds(
+            Drone::RETREAT_TIME_SEC,
+            false,
+        ));
+        if hull.block() {
+            continue;
+        }
+        match drone.kind {
+            DroneKind::Thief => {
+                fuel.burn(Drone::STEAL_AMOUNT);
+            }
+            DroneKind::Pusher => {
+                let away = (player_transform.translation
+                    - transform.translation)
+                    .truncate()
+                    .normalize_or_zero();
+                push.0 = Some(away * Drone::PUSH_SPEED);
+            }
+        }
+    }
+}
+/// hands a queued push to rapier as an impulse in the next physics step
+fn push_player(
+    mut player_query: Query<
+        (&mut ExternalImpulse, &ReadMassProperties),
+        With<PlayerLabel>,
+    >,
+    mut push: ResMut<DronePush>,
+) {
+    let change = match push.0.take() {
+        Some(change) => change,
+        None => return,
+    };
+    for (mut impulse, mass) in player_query.iter_mut() {
+        impulse.impulse = change * mass.0.mass;
+    }
+}
+#[allow(clippy::type_complexity)]
+fn clean_up_drones(
+    mut commands: Commands,
+    mut p_set: ParamSet<(
+        Query<(Entity, &mut Transform), With<Drone>>,
+        Query<&Transform, With<PlayerLabel>>,
+    )>,
+    edge: Res<WorldEdge>,
+) {
+    let player_y = match p_set.p1().iter().next() {
+        Some(transform) => transform.translation.y,
+        None => {
+            error!("player not found");
+            return;
+        }
+    };
+    for (entity, mut transform) in p_set.p0().iter_mut() {
+        if (transform.translation.y - player_y).abs() > FUEL_MAX_Y_DIST
+            || !edge.contain(&mut transform.translation)
+        {
+            commands.entity(entity).despawn_recursive();
+        }
+    }
+}
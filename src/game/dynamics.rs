@@ -0,0 +1,97 @@
+use super::{
+    ActiveModifiers, FixedPhysicsStage, GameLabel, GameState, PlayerLabel,
+};
+use bevy::prelude::*;
+use bevy_rapier2d::prelude::*;
+
+pub struct DynamicsPlugin;
+impl Plugin for DynamicsPlugin {
+    fn build(&self, app: &mut App) {
+        // run every physics step so the speed limit holds at any frame rate
+        app.stage(FixedPhysicsStage::Step, |schedule: &mut Schedule| {
+            schedule.add_system_set_to_stage(
+                FixedPhysicsStage::Flight,
+                SystemSet::on_update(GameState::Game)
+                    .with_system(apply_gravity_scale.after(GameLabel::Thrust))
+                    .with_system(apply_restitution.after(GameLabel::Thrust))
+                    .with_system(limit_speed.after(GameLabel::Thrust)),
+            )
+        });
+    }
+}
+/// How a ship handles, applied to rapier through [`GravityScale`],
+/// [`Damping`], [`Restitution`] and a speed limit
+#[derive(Component, Clone, Copy, Debug)]
+pub struct FlightDynamics {
+    /// multiplier on world gravity
+    pub gravity_scale: f32,
+    /// linear damping on top of the drag of the current biome
+    pub linear_damping: f32,
+    /// speed in pixels per second the ship can never exceed
+    pub max_speed: f32,
+    /// bounciness when hitting the ground or obstacles
+    pub restitution: f32,
+    /// acceleration of the engine at rest in pixels per second squared
+    pub thrust: f32,
+    /// shape of the thrust curve, 0.0 keeps full thrust up to the max speed
+    /// while larger values fade it out earlier
+    pub thrust_falloff: f32,
+}
+impl FlightDynamics {
+    /// Fraction of full thrust available when already moving at `speed` in
+    /// the direction of thrust. Thrusting against the motion is never
+    /// weakened.
+    pub fn thrust_factor(&self, speed: f32) -> f32 {
+        let headroom = (1.0 - speed.max(0.0) / self.max_speed).clamp(0.0, 1.0);
+        headroom.powf(self.thrust_falloff)
+    }
+}
+impl Default for FlightDynamics {
+    fn default() -> Self {
+        Self {
+            gravity_scale: 1.0,
+            linear_damping: 0.0,
+            max_speed: 2500.0,
+            restitution: 0.3,
+            thrust: 1000.0,
+            thrust_falloff: 0.5,
+        }
+    }
+}
+fn apply_gravity_scale(
+    mut player_query: Query<
+        (&mut GravityScale, &FlightDynamics),
+        With<PlayerLabel>,
+    >,
+    modifiers: Res<ActiveModifiers>,
+) {
+    for (mut gravity, dynamics) in player_query.iter_mut() {
+        let scale = dynamics.gravity_scale * modifiers.gravity_scale();
+        if gravity.0 != scale {
+            gravity.0 = scale;
+        }
+    }
+}
+fn apply_restitution(
+    mut player_query: Query<
+        (&mut Restitution, &FlightDynamics),
+        Changed<FlightDynamics>,
+    >,
+) {
+    for (mut restitution, dynamics) in player_query.iter_mut() {
+        restitution.coefficient = dynamics.restitution;
+    }
+}
+fn limit_speed(
+    mut player_query: Query<
+        (&mut Velocity, &FlightDynamics),
+        With<PlayerLabel>,
+    >,
+) {
+    for (mut velocity, dynamics) in player_query.iter_mut() {
+        let speed = velocity.linvel.length();
+        if speed > dynamics.max_speed {
+            velocity.linvel *= dynamics.max_speed / speed;
+        }
+    }
+}
@@ -0,0 +1,223 @@
+use super::{
+    spawn_particle, FixedPhysicsStage, GameLabel, GameState, Particle,
+    ParticleSpec, PlayerLabel, MAX_PARTICLES,
+};
+use bevy::{prelude::*, sprite::MaterialMesh2dBundle};
+use bevy_rapier2d::prelude::*;
+use bevy_turborand::*;
+
+pub struct FieldPlugin;
+impl Plugin for FieldPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<FieldAssets>()
+            .add_system_set(
+                SystemSet::on_update(GameState::Game)
+                    .with_system(emit_field_particles),
+            )
+            .stage(FixedPhysicsStage::Step, |schedule: &mut Schedule| {
+                schedule.add_system_set_to_stage(
+                    FixedPhysicsStage::Flight,
+                    SystemSet::on_update(GameState::Game)
+                        .with_system(apply_fields.after(GameLabel::Thrust)),
+                )
+            });
+    }
+}
+/// How a [`ForceField`] pushes the ship
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum FieldKind {
+    /// horizontal push across a band, negative blows to the left
+    Wind { accel: f32 },
+    /// upward push that takes some of the work off the engine
+    Updraft { accel: f32 },
+    /// pull toward the center, strongest there and fading out at `radius`
+    GravityWell { accel: f32, radius: f32 },
+}
+impl FieldKind {
+    /// acceleration of something at `offset` from the center of the field
+    pub fn acceleration(&self, offset: Vec2) -> Vec2 {
+        match *self {
+            FieldKind::Wind { accel } => Vec2::new(accel, 0.0),
+            FieldKind::Updraft { accel } => Vec2::new(0.0, accel),
+            FieldKind::GravityWell { accel, radius } => {
+                let falloff = 1.0 - (offset.length() / radius).min(1.0);
+                -offset.normalize_or_zero() * accel * falloff
+            }
+        }
+    }
+}
+/// Volume of the world that pushes the ship while it is inside
+#[derive(Component)]
+pub struct ForceField {
+    pub kind: FieldKind,
+    /// extent of the field, the diameter for gravity wells
+    size: Vec2,
+    /// time between particles showing which way the field pushes
+    emit: Timer,
+}
+impl ForceField {
+    const EMIT_TIME_SEC: f32 = 0.08;
+}
+/// Between the background and the world, under the particles
+const FIELD_Z: f32 = -0.01;
+/// Mesh and material shared by every gravity well, a unit circle scaled
+/// to the radius of each one
+pub struct FieldAssets {
+    well_mesh: Handle<Mesh>,
+    well_material: Handle<ColorMaterial>,
+}
+impl FromWorld for FieldAssets {
+    fn from_world(world: &mut World) -> Self {
+        let well_mesh = world
+            .resource_mut::<Assets<Mesh>>()
+            .add(shape::Circle::new(1.0).into());
+        let well_material = world
+            .resource_mut::<Assets<ColorMaterial>>()
+            .add(ColorMaterial::from(colors::WELL_COLOR));
+        Self {
+            well_mesh,
+            well_material,
+        }
+    }
+}
+mod colors {
+    use bevy::prelude::*;
+
+    pub const WIND_COLOR: Color = Color::rgba(0.8, 0.9, 1.0, 0.06);
+    pub const UPDRAFT_COLOR: Color = Color::rgba(1.0, 0.85, 0.5, 0.08);
+    pub const WELL_COLOR: Color = Color::rgba(0.6, 0.3, 0.9, 0.15);
+    pub const WIND_PARTICLE: Color = Color::rgba(0.8, 0.9, 1.0, 0.5);
+    pub const UPDRAFT_PARTICLE: Color = Color::rgba(1.0, 0.85, 0.5, 0.5);
+    pub const WELL_PARTICLE: Color = Color::rgba(0.7, 0.4, 1.0, 0.6);
+    /// particles fade out as they cross the field
+    pub const PARTICLE_END: Color = Color::rgba(1.0, 1.0, 1.0, 0.0);
+}
+/// spawns a field centered at `center` as a child of a level chunk
+pub fn spawn_force_field(
+    parent: &mut ChildBuilder,
+    center: Vec2,
+    size: Vec2,
+    kind: FieldKind,
+    assets: &FieldAssets,
+) {
+    let transform = Transform::from_translation(center.extend(FIELD_Z));
+    let mut field = match kind {
+        FieldKind::GravityWell { radius, .. } => {
+            let mut field = parent.spawn_bundle(MaterialMesh2dBundle {
+                mesh: assets.well_mesh.clone().into(),
+                material: assets.well_material.clone(),
+                // rapier scales the collider along with the mesh
+                transform: transform.with_scale(Vec3::new(radius, radius, 1.0)),
+                ..default()
+            });
+            field.insert(Collider::ball(1.0));
+            field
+        }
+        FieldKind::Wind { .. } | FieldKind::Updraft { .. } => {
+            let color = if matches!(kind, FieldKind::Wind { .. }) {
+                colors::WIND_COLOR
+            } else {
+                colors::UPDRAFT_COLOR
+            };
+            let mut field = parent.spawn_bundle(SpriteBundle {
+                sprite: Sprite {
+                    color,
+                    custom_size: Some(size),
+                    ..default()
+                },
+                transform,
+                ..default()
+            });
+            field.insert(Collider::cuboid(0.5 * size.x, 0.5 * size.y));
+            field
+        }
+    };
+    field.insert(Sensor).insert(ForceField {
+        kind,
+        size,
+        emit: Timer::from_seconds(ForceField::EMIT_TIME_SEC, true),
+    });
+}
+/// adds the push of every field the ship is in on top of its thrust
+fn apply_fields(
+    mut player_query: Query<
+        (Entity, &Transform, &mut ExternalForce, &ReadMassProperties),
+        With<PlayerLabel>,
+    >,
+    field_query: Query<(Entity, &GlobalTransform, &ForceField)>,
+    rapier_context: Res<RapierContext>,
+) {
+    for (player, transform, mut force, mass) in player_query.iter_mut() {
+        let position = transform.translation.truncate();
+        let accel: Vec2 = field_query
+            .iter()
+            .filter(|(field, _, _)| {
+                rapier_context.intersection_pair(player, *field) == Some(true)
+            })
+            .map(|(_, field_transform, field)| {
+                let center = field_transform.translation().truncate();
+                field.kind.acceleration(position - center)
+            })
+            .fold(Vec2::ZERO, |total, accel| total + accel);
+        force.force += accel * mass.0.mass;
+    }
+}
+fn emit_field_particles(
+    mut commands: Commands,
+    mut field_query: Query<(&GlobalTransform, &mut ForceField)>,
+    particle_query: Query<(), With<Particle>>,
+    mut global_rng: ResMut<GlobalRng>,
+    time: Res<Time>,
+) {
+    let mut budget = MAX_PARTICLES.saturating_sub(particle_query.iter().len());
+    for (transform, mut field) in field_query.iter_mut() {
+        if !field.emit.tick(time.delta()).just_finished() || budget == 0 {
+            continue;
+        }
+        let center = transform.translation().truncate();
+        let spec = match field.kind {
+            FieldKind::Wind { accel } => ParticleSpec {
+                position: center
+                    + 0.5
+                        * field.size
+                        * Vec2::new(
+                            -accel.signum(),
+                            global_rng.f32_normalized(),
+                        ),
+                velocity: Vec2::new(accel.signum() * 400.0, 0.0),
+                lifetime: field.size.x / 400.0,
+                start_color: colors::WIND_PARTICLE,
+                end_color: colors::PARTICLE_END,
+                start_size: 4.0,
+                end_size: 4.0,
+            },
+            FieldKind::Updraft { .. } => ParticleSpec {
+                position: center
+                    + 0.5
+                        * field.size
+                        * Vec2::new(global_rng.f32_normalized(), -1.0),
+                velocity: Vec2::new(0.0, 300.0),
+                lifetime: field.size.y / 300.0,
+                start_color: colors::UPDRAFT_PARTICLE,
+                end_color: colors::PARTICLE_END,
+                start_size: 5.0,
+                end_size: 2.0,
+            },
+            FieldKind::GravityWell { radius, .. } => {
+                let angle = std::f32::consts::TAU * global_rng.f32();
+                let direction = Vec2::new(angle.cos(), angle.sin());
+                ParticleSpec {
+                    position: center + direction * radius,
+                    velocity: -direction * radius,
+                    lifetime: 1.0,
+                    start_color: colors::WELL_PARTICLE,
+                    end_color: colors::WELL_PARTICLE,
+                    start_size: 3.0,
+                    end_size: 6.0,
+                }
+            }
+        };
+        spawn_particle(&mut commands, spec);
+        budget -= 1;
+    }
+}
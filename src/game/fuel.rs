@@ -0,0 +1,182 @@
+use bevy::prelude::*;
+
+pub struct FuelPlugin;
+impl Plugin for FuelPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(FuelEconomy::default());
+    }
+}
+/// Rules for how much fuel pickups give and thrust costs
+#[derive(Clone, Copy, Debug)]
+pub struct FuelEconomy {
+    /// fuel in a pickup at ground level
+    pub base_pickup: f32,
+    /// extra fuel in a pickup per pixel of altitude, pickups get richer
+    /// as they get rarer
+    pub pickup_per_altitude: f32,
+    /// most fuel a single pickup can give
+    pub max_pickup: f32,
+    /// size of the reserve tank as a fraction of the main tank
+    pub reserve_fraction: f32,
+    /// fuel burned per pixel per second of velocity change
+    pub burn_rate: f32,
+}
+impl FuelEconomy {
+    /// fuel given by a pickup collected at `altitude`
+    pub fn pickup_value(&self, altitude: f32) -> f32 {
+        (self.base_pickup + altitude.max(0.0) * self.pickup_per_altitude)
+            .min(self.max_pickup)
+    }
+    /// fuel needed to change velocity by `delta_v`
+    pub fn burn(&self, delta_v: f32) -> f32 {
+        delta_v.max(0.0) * self.burn_rate
+    }
+    /// velocity change bought by burning `fuel`
+    pub fn delta_v(&self, fuel: f32) -> f32 {
+        fuel.max(0.0) / self.burn_rate
+    }
+    /// tanks for a ship with `capacity` fuel in its main tank
+    pub fn tanks(&self, capacity: f32) -> PlayerFuel {
+        PlayerFuel::new(capacity, capacity * self.reserve_fraction)
+    }
+}
+impl Default for FuelEconomy {
+    fn default() -> Self {
+        Self {
+            base_pickup: 100.0,
+            pickup_per_altitude: 0.01,
+            max_pickup: 300.0,
+            reserve_fraction: 0.25,
+            burn_rate: 1.0,
+        }
+    }
+}
+/// Fuel carried by the ship. Fuel beyond a full main tank spills into the
+/// reserve tank, which is only burned once the main tank is empty. Whatever
+/// fits in neither is lost.
+#[derive(Component, Clone, Debug)]
+pub struct PlayerFuel {
+    amount: f32,
+    max: f32,
+    reserve: f32,
+    reserve_max: f32,
+}
+impl PlayerFuel {
+    /// starts with a full main tank and an empty reserve
+    pub fn new(max: f32, reserve_max: f32) -> Self {
+        Self {
+            amount: max,
+            max,
+            reserve: 0.0,
+            reserve_max,
+        }
+    }
+    /// fuel left in both tanks
+    pub fn get_fuel(&self) -> f32 {
+        self.amount + self.reserve
+    }
+    /// gets percent of the main tank left in range 0.0 to 1.0
+    pub fn get_fuel_ratio_left(&self) -> f32 {
+        self.amount / self.max
+    }
+    /// gets percent of the reserve tank left in range 0.0 to 1.0
+    pub fn get_reserve_ratio_left(&self) -> f32 {
+        if self.reserve_max > 0.0 {
+            self.reserve / self.reserve_max
+        } else {
+            0.0
+        }
+    }
+    /// fills the main tank then the reserve, returns the fuel that fit
+    pub fn add_fuel(&mut self, amount: f32) -> f32 {
+        let amount = amount.max(0.0);
+        let main = amount.min(self.max - self.amount);
+        let reserve = (amount - main).min(self.reserve_max - self.reserve);
+        self.amount += main;
+        self.reserve += reserve;
+        main + reserve
+    }
+    /// burns from the main tank then the reserve, returns the fuel that was
+    /// actually available
+    pub fn burn(&mut self, amount: f32) -> f32 {
+        let amount = amount.max(0.0);
+        let main = amount.min(self.amount);
+        let reserve = (amount - main).min(self.reserve);
+        self.amount -= main;
+        self.reserve -= reserve;
+        main + reserve
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pickup_never_fills_past_the_main_tank() {
+        let mut fuel = PlayerFuel::new(1000.0, 0.0);
+        fuel.burn(500.0);
+        assert_eq!(fuel.add_fuel(100.0), 100.0);
+        assert_eq!(fuel.get_fuel(), 600.0);
+    }
+    #[test]
+    fn overfill_spills_into_reserve_and_the_rest_is_lost() {
+        let mut fuel = PlayerFuel::new(1000.0, 250.0);
+        fuel.burn(50.0);
+        assert_eq!(fuel.add_fuel(100.0), 100.0);
+        assert_eq!(fuel.get_fuel_ratio_left(), 1.0);
+        assert_eq!(fuel.get_reserve_ratio_left(), 0.2);
+        assert_eq!(fuel.add_fuel(300.0), 200.0);
+        assert_eq!(fuel.get_fuel(), 1250.0);
+    }
+    #[test]
+    fn reserve_is_burned_after_the_main_tank() {
+        let mut fuel = PlayerFuel::new(100.0, 50.0);
+        fuel.add_fuel(50.0);
+        assert_eq!(fuel.burn(80.0), 80.0);
+        assert_eq!(fuel.get_reserve_ratio_left(), 1.0);
+        assert_eq!(fuel.burn(40.0), 40.0);
+        assert_eq!(fuel.get_fuel_ratio_left(), 0.0);
+        assert_eq!(fuel.get_reserve_ratio_left(), 0.6);
+    }
+    #[test]
+    fn burning_stops_at_empty() {
+        let mut fuel = PlayerFuel::new(100.0, 50.0);
+        assert_eq!(fuel.burn(300.0), 100.0);
+        assert_eq!(fuel.get_fuel(), 0.0);
+        assert_eq!(fuel.burn(10.0), 0.0);
+    }
+    #[test]
+    fn negative_amounts_are_ignored() {
+        let mut fuel = PlayerFuel::new(100.0, 50.0);
+        assert_eq!(fuel.add_fuel(-10.0), 0.0);
+        assert_eq!(fuel.burn(-10.0), 0.0);
+        assert_eq!(fuel.get_fuel(), 100.0);
+    }
+    #[test]
+    fn pickups_grow_with_altitude_up_to_the_cap() {
+        let economy = FuelEconomy::default();
+        assert_eq!(economy.pickup_value(-500.0), economy.base_pickup);
+        assert_eq!(economy.pickup_value(0.0), economy.base_pickup);
+        assert!(economy.pickup_value(5000.0) > economy.pickup_value(1000.0));
+        assert_eq!(economy.pickup_value(1.0e6), economy.max_pickup);
+    }
+    #[test]
+    fn burn_scales_with_thrust() {
+        let economy = FuelEconomy {
+            burn_rate: 2.0,
+            ..default()
+        };
+        assert_eq!(economy.burn(0.0), 0.0);
+        assert_eq!(economy.burn(10.0), 20.0);
+        assert_eq!(economy.burn(20.0), 2.0 * economy.burn(10.0));
+        assert_eq!(economy.delta_v(economy.burn(15.0)), 15.0);
+    }
+    #[test]
+    fn tanks_get_a_reserve_sized_by_the_economy() {
+        let economy = FuelEconomy::default();
+        let mut fuel = economy.tanks(1000.0);
+        assert_eq!(fuel.get_fuel(), 1000.0);
+        assert_eq!(fuel.add_fuel(1000.0), 250.0);
+    }
+}
@@ -0,0 +1,85 @@
+use super::{Checkpoints, GameLabel, GameState, PlayerLabel, PlayerScore};
+use bevy::prelude::*;
+
+/// Records the path of every run and replays the highest one as a ghost
+/// ship on the minimap. Only kept until the game is closed.
+pub struct GhostPlugin;
+impl Plugin for GhostPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(Ghost::default())
+            .add_system_set(
+                SystemSet::on_enter(GameState::Game)
+                    .with_system(start_recording.after(GameLabel::Checkpoint)),
+            )
+            .add_system_set(
+                SystemSet::on_update(GameState::Game).with_system(record_path),
+            )
+            .add_system_set(
+                SystemSet::on_exit(GameState::Game).with_system(keep_best_path),
+            );
+    }
+}
+/// Path of the highest run and the path of the run being played
+#[derive(Default)]
+pub struct Ghost {
+    /// positions of the best run, one every [`Ghost::SAMPLE_TIME`]
+    best: Vec<Vec2>,
+    /// highest altitude the best run reached
+    best_altitude: f32,
+    current: Vec<Vec2>,
+    /// runs continued from a checkpoint start part way up, so they are
+    /// neither recorded nor raced against
+    recording: bool,
+    timer: Timer,
+}
+impl Ghost {
+    /// seconds between recorded positions
+    const SAMPLE_TIME: f32 = 0.1;
+    /// where the best run was this far into its run, it stays at its last
+    /// position once the current run has lasted longer
+    pub fn position(&self) -> Option<Vec2> {
+        if !self.recording {
+            return None;
+        }
+        self.best
+            .get(self.current.len().min(self.best.len().max(1) - 1))
+            .copied()
+    }
+}
+fn start_recording(mut ghost: ResMut<Ghost>, checkpoints: Res<Checkpoints>) {
+    ghost.current.clear();
+    ghost.recording = checkpoints.resumed().is_none();
+    ghost.timer = Timer::from_seconds(Ghost::SAMPLE_TIME, true);
+}
+fn record_path(
+    player_query: Query<&Transform, With<PlayerLabel>>,
+    mut ghost: ResMut<Ghost>,
+    time: Res<Time>,
+) {
+    if !ghost.recording {
+        return;
+    }
+    let position = match player_query.iter().next() {
+        Some(transform) => transform.translation.truncate(),
+        None => {
+            error!("player not found");
+            return;
+        }
+    };
+    for _ in 0..ghost.timer.tick(time.delta()).times_finished_this_tick() {
+        ghost.current.push(position);
+    }
+}
+fn keep_best_path(
+    player_query: Query<&PlayerScore, With<PlayerLabel>>,
+    mut ghost: ResMut<Ghost>,
+) {
+    let altitude = match player_query.iter().next() {
+        Some(score) => score.get_altitude(),
+        None => return,
+    };
+    if ghost.recording && altitude > ghost.best_altitude {
+        ghost.best = std::mem::take(&mut ghost.current);
+        ghost.best_altitude = altitude;
+    }
+}
@@ -0,0 +1,141 @@
+use super::{
+    CurrentBiome, GameState, PlayerCoolingTimer, PlayerLabel, PlayerThrust,
+};
+use bevy::prelude::*;
+
+pub struct HeatPlugin;
+impl Plugin for HeatPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(CoolingModel::Timer)
+            .add_event::<HeatWarning>()
+            .add_system_set(
+                SystemSet::on_update(GameState::Game)
+                    .with_system(heat_ship)
+                    .with_system(warn_heat.after(heat_ship)),
+            );
+    }
+}
+/// How the ship's cooling is used up
+#[derive(Clone, Copy, Eq, PartialEq, Debug)]
+pub enum CoolingModel {
+    /// cooling runs out at a steady rate and pickups refill it
+    Timer,
+    /// thrusting heats the ship and coasting sheds the heat again, pickups
+    /// only refuel
+    Heat,
+}
+impl CoolingModel {
+    /// seconds of cooling used per second of thrust
+    const HEAT_RATE: f32 = 1.5;
+    /// seconds of cooling recovered per second of coasting at ground level
+    /// in the lower atmosphere
+    const SHED_RATE: f32 = 0.6;
+    /// altitude at which coasting sheds heat twice as fast as on the ground
+    const SHED_ALTITUDE: f32 = 40000.0;
+    pub fn name(&self) -> &'static str {
+        match self {
+            CoolingModel::Timer => "TIMER",
+            CoolingModel::Heat => "HEAT",
+        }
+    }
+    pub fn next(&self) -> Self {
+        match self {
+            CoolingModel::Timer => CoolingModel::Heat,
+            CoolingModel::Heat => CoolingModel::Timer,
+        }
+    }
+    /// seconds of cooling recovered per second of coasting, thinner air
+    /// carries heat away faster just like it slows the timer down
+    pub fn shed_rate(current_biome: &CurrentBiome) -> f32 {
+        Self::SHED_RATE
+            * (1.0 + current_biome.altitude.max(0.0) / Self::SHED_ALTITUDE)
+            / current_biome.biome.cooling_drain()
+    }
+    /// seconds until the ship overheats at the current rate, `None` while
+    /// it is cooling down
+    pub fn seconds_to_overheat(
+        &self,
+        cooling: &PlayerCoolingTimer,
+        thrusting: bool,
+        current_biome: &CurrentBiome,
+    ) -> Option<f32> {
+        let left = cooling.seconds_left();
+        match self {
+            CoolingModel::Timer => {
+                Some(left / current_biome.biome.cooling_drain())
+            }
+            CoolingModel::Heat if thrusting => Some(left / Self::HEAT_RATE),
+            CoolingModel::Heat => None,
+        }
+    }
+}
+/// How close the ship is to overheating
+#[derive(Clone, Copy, Eq, PartialEq, PartialOrd, Debug)]
+pub enum HeatLevel {
+    Normal,
+    Hot,
+    Critical,
+}
+impl HeatLevel {
+    /// fraction of cooling used at which the ship runs hot
+    pub const HOT: f32 = 0.6;
+    /// fraction of cooling used at which overheating is imminent
+    pub const CRITICAL: f32 = 0.85;
+    pub fn from_frac_used(used: f32) -> Self {
+        if used >= Self::CRITICAL {
+            HeatLevel::Critical
+        } else if used >= Self::HOT {
+            HeatLevel::Hot
+        } else {
+            HeatLevel::Normal
+        }
+    }
+}
+/// Sent when the ship heats up past a [`HeatLevel`] threshold
+pub struct HeatWarning(pub HeatLevel);
+/// Heat level of the ship when it was last checked for a [`HeatWarning`],
+/// starts over with every new ship
+#[derive(Component)]
+pub struct LastHeatLevel(HeatLevel);
+impl Default for LastHeatLevel {
+    fn default() -> Self {
+        Self(HeatLevel::Normal)
+    }
+}
+fn heat_ship(
+    mut player_query: Query<
+        (&mut PlayerCoolingTimer, &PlayerThrust),
+        With<PlayerLabel>,
+    >,
+    cooling_model: Res<CoolingModel>,
+    current_biome: Res<CurrentBiome>,
+    time: Res<Time>,
+) {
+    if *cooling_model != CoolingModel::Heat {
+        return;
+    }
+    for (mut cooling, thrust) in player_query.iter_mut() {
+        if thrust.is_thrusting() {
+            cooling.add_heat(CoolingModel::HEAT_RATE * time.delta_seconds());
+        } else {
+            cooling.shed_heat(
+                CoolingModel::shed_rate(&current_biome) * time.delta_seconds(),
+            );
+        }
+    }
+}
+fn warn_heat(
+    mut player_query: Query<
+        (&PlayerCoolingTimer, &mut LastHeatLevel),
+        With<PlayerLabel>,
+    >,
+    mut warnings: EventWriter<HeatWarning>,
+) {
+    for (cooling, mut last_level) in player_query.iter_mut() {
+        let level = HeatLevel::from_frac_used(cooling.get_frac_used());
+        if level > last_level.0 {
+            warnings.send(HeatWarning(level));
+        }
+        last_level.0 = level;
+    }
+}
@@ -0,0 +1,404 @@
+use super::{
+    meters, CoolingModel, CurrentBiome, GameEntity, GameState, HeatLevel,
+    PlayerCoolingTimer, PlayerLabel, PlayerProfile, PlayerThrust,
+    PIXELS_PER_METER,
+};
+use crate::loading::FontAssets;
+use bevy::prelude::*;
+use bevy_rapier2d::prelude::*;
+
+/// Gauges drawn along the sides of the screen during a run
+pub struct HudPlugin;
+impl Plugin for HudPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_system_set(
+            SystemSet::on_enter(GameState::Game).with_system(spawn_hud),
+        )
+        .add_system_set(
+            SystemSet::on_update(GameState::Game)
+                .with_system(scale_hud_text)
+                .with_system(update_altimeter)
+                .with_system(update_vertical_speed)
+                .with_system(update_heat_gauge)
+                .with_system(update_overheat_countdown),
+        );
+    }
+}
+/// Text that keeps its size relative to the window height
+#[derive(Component)]
+struct HudText {
+    /// font size at [`HudText::REFERENCE_HEIGHT`]
+    size: f32,
+}
+impl HudText {
+    const REFERENCE_HEIGHT: f32 = 720.0;
+}
+/// part of the altimeter track filled up to the current altitude
+#[derive(Component)]
+struct AltimeterFill;
+/// line on the altimeter at the personal best
+#[derive(Component)]
+struct BestMarker;
+#[derive(Component)]
+struct AltitudeText;
+#[derive(Component)]
+struct BestText;
+#[derive(Component)]
+struct VerticalSpeedText;
+/// part of the heat gauge filled up to the cooling used
+#[derive(Component)]
+struct HeatFill;
+#[derive(Component)]
+struct HeatText;
+/// seconds left until overheating, only shown while the heat is critical
+#[derive(Component)]
+struct OverheatCountdown;
+/// altitude in pixels the altimeter covers at least
+const ALTIMETER_MIN_RANGE: f32 = 5000.0;
+/// headroom above the higher of the current altitude and the personal best
+const ALTIMETER_HEADROOM: f32 = 1.25;
+mod colors {
+    use bevy::prelude::*;
+
+    pub const TEXT_SIZE: f32 = 24.0;
+    pub const COUNTDOWN_SIZE: f32 = 64.0;
+    pub const TEXT_COLOR: Color = Color::BLACK;
+    pub const TRACK_COLOR: Color = Color::rgba(0.0, 0.0, 0.0, 0.3);
+    pub const ALTIMETER_COLOR: Color = Color::rgb(0.2, 0.5, 1.0);
+    pub const BEST_COLOR: Color = Color::rgb(1.0, 0.85, 0.0);
+    pub const HEAT_COLOR: Color = Color::rgb(1.0, 0.3, 0.1);
+    pub const HOT_ZONE_COLOR: Color = Color::rgba(1.0, 0.6, 0.1, 0.4);
+    pub const CRITICAL_ZONE_COLOR: Color = Color::rgba(1.0, 0.1, 0.1, 0.5);
+    pub const COUNTDOWN_COLOR: Color = Color::rgb(1.0, 0.1, 0.1);
+    pub const COUNTDOWN_TICK_COLOR: Color = Color::rgb(1.0, 1.0, 1.0);
+}
+fn spawn_text(
+    commands: &mut Commands,
+    font: Handle<Font>,
+    size: f32,
+    color: Color,
+    position: UiRect<Val>,
+) -> Entity {
+    commands
+        .spawn_bundle(
+            TextBundle::from_section(
+                "",
+                TextStyle {
+                    font,
+                    font_size: size,
+                    color,
+                },
+            )
+            .with_style(Style {
+                position_type: PositionType::Absolute,
+                position,
+                ..default()
+            }),
+        )
+        .insert(HudText { size })
+        .insert(GameEntity)
+        .id()
+}
+/// vertical gauge filled from the bottom, `zones` are drawn over the fill
+/// as (bottom, height, color) in fractions of the gauge, returns the gauge
+/// and its fill
+fn spawn_gauge(
+    commands: &mut Commands,
+    position: UiRect<Val>,
+    fill_color: Color,
+    zones: &[(f32, f32, Color)],
+) -> (Entity, Entity) {
+    let mut fill = None;
+    let gauge = commands
+        .spawn_bundle(NodeBundle {
+            style: Style {
+                position_type: PositionType::Absolute,
+                position,
+                size: Size::new(Val::Percent(2.5), Val::Percent(60.0)),
+                // columns stack upward, so the fill grows from the bottom
+                flex_direction: FlexDirection::Column,
+                ..default()
+            },
+            color: colors::TRACK_COLOR.into(),
+            ..default()
+        })
+        .insert(GameEntity)
+        .with_children(|parent| {
+            fill = Some(
+                parent
+                    .spawn_bundle(NodeBundle {
+                        style: Style {
+                            size: Size::new(
+                                Val::Percent(100.0),
+                                Val::Percent(0.0),
+                            ),
+                            ..default()
+                        },
+                        color: fill_color.into(),
+                        ..default()
+                    })
+                    .id(),
+            );
+            for (bottom, height, color) in zones {
+                parent.spawn_bundle(NodeBundle {
+                    style: Style {
+                        position_type: PositionType::Absolute,
+                        position: UiRect {
+                            bottom: Val::Percent(100.0 * bottom),
+                            ..default()
+                        },
+                        size: Size::new(
+                            Val::Percent(100.0),
+                            Val::Percent(100.0 * height),
+                        ),
+                        ..default()
+                    },
+                    color: (*color).into(),
+                    ..default()
+                });
+            }
+        })
+        .id();
+    (gauge, fill.unwrap())
+}
+fn spawn_hud(mut commands: Commands, fonts: Res<FontAssets>) {
+    let (altimeter, fill) = spawn_gauge(
+        &mut commands,
+        UiRect {
+            right: Val::Percent(2.0),
+            top: Val::Percent(20.0),
+            ..default()
+        },
+        colors::ALTIMETER_COLOR,
+        &[],
+    );
+    let best_marker = commands
+        .spawn_bundle(NodeBundle {
+            style: Style {
+                position_type: PositionType::Absolute,
+                size: Size::new(Val::Percent(100.0), Val::Px(4.0)),
+                ..default()
+            },
+            color: colors::BEST_COLOR.into(),
+            ..default()
+        })
+        .insert(BestMarker)
+        .id();
+    commands.entity(altimeter).add_child(best_marker);
+    commands.entity(fill).insert(AltimeterFill);
+    let text = spawn_text(
+        &mut commands,
+        fonts.silkscreen_bold.clone(),
+        colors::TEXT_SIZE,
+        colors::TEXT_COLOR,
+        UiRect {
+            right: Val::Percent(2.0),
+            top: Val::Percent(13.0),
+            ..default()
+        },
+    );
+    commands.entity(text).insert(AltitudeText);
+    let text = spawn_text(
+        &mut commands,
+        fonts.silkscreen.clone(),
+        colors::TEXT_SIZE,
+        colors::BEST_COLOR,
+        UiRect {
+            right: Val::Percent(2.0),
+            top: Val::Percent(82.0),
+            ..default()
+        },
+    );
+    commands.entity(text).insert(BestText);
+    let text = spawn_text(
+        &mut commands,
+        fonts.silkscreen.clone(),
+        colors::TEXT_SIZE,
+        colors::TEXT_COLOR,
+        UiRect {
+            right: Val::Percent(2.0),
+            top: Val::Percent(87.0),
+            ..default()
+        },
+    );
+    commands.entity(text).insert(VerticalSpeedText);
+
+    let (_, heat_fill) = spawn_gauge(
+        &mut commands,
+        UiRect {
+            left: Val::Percent(2.0),
+            top: Val::Percent(20.0),
+            ..default()
+        },
+        colors::HEAT_COLOR,
+        &[
+            (
+                HeatLevel::HOT,
+                HeatLevel::CRITICAL - HeatLevel::HOT,
+                colors::HOT_ZONE_COLOR,
+            ),
+            (
+                HeatLevel::CRITICAL,
+                1.0 - HeatLevel::CRITICAL,
+                colors::CRITICAL_ZONE_COLOR,
+            ),
+        ],
+    );
+    commands.entity(heat_fill).insert(HeatFill);
+    let text = spawn_text(
+        &mut commands,
+        fonts.silkscreen_bold.clone(),
+        colors::TEXT_SIZE,
+        colors::TEXT_COLOR,
+        UiRect {
+            left: Val::Percent(2.0),
+            top: Val::Percent(13.0),
+            ..default()
+        },
+    );
+    commands.entity(text).insert(HeatText);
+    let text = spawn_text(
+        &mut commands,
+        fonts.silkscreen_bold.clone(),
+        colors::COUNTDOWN_SIZE,
+        colors::COUNTDOWN_COLOR,
+        UiRect {
+            left: Val::Percent(35.0),
+            top: Val::Percent(25.0),
+            ..default()
+        },
+    );
+    commands.entity(text).insert(OverheatCountdown);
+}
+fn scale_hud_text(
+    windows: Res<Windows>,
+    mut text_query: Query<(&HudText, &mut Text), ()>,
+) {
+    let height = windows
+        .get_primary()
+        .map_or(HudText::REFERENCE_HEIGHT, |window| window.height());
+    let scale = height / HudText::REFERENCE_HEIGHT;
+    for (hud_text, mut text) in text_query.iter_mut() {
+        let size = hud_text.size * scale;
+        for section in text.sections.iter_mut() {
+            if section.style.font_size != size {
+                section.style.font_size = size;
+            }
+        }
+    }
+}
+#[allow(clippy::type_complexity)]
+fn update_altimeter(
+    player_query: Query<&Transform, With<PlayerLabel>>,
+    mut fill_query: Query<&mut Style, With<AltimeterFill>>,
+    mut marker_query: Query<
+        &mut Style,
+        (With<BestMarker>, Without<AltimeterFill>),
+    >,
+    mut text_query: ParamSet<(
+        Query<&mut Text, With<AltitudeText>>,
+        Query<&mut Text, With<BestText>>,
+    )>,
+    profile: Res<PlayerProfile>,
+) {
+    let altitude = match player_query.iter().next() {
+        Some(transform) => transform.translation.y.max(0.0),
+        None => {
+            error!("player not found");
+            return;
+        }
+    };
+    let best = profile.best_altitude;
+    let range =
+        (altitude.max(best) * ALTIMETER_HEADROOM).max(ALTIMETER_MIN_RANGE);
+    for mut style in fill_query.iter_mut() {
+        style.size.height = Val::Percent(100.0 * altitude / range);
+    }
+    for mut style in marker_query.iter_mut() {
+        style.position.bottom = Val::Percent(100.0 * best / range);
+    }
+    for mut text in text_query.p0().iter_mut() {
+        text.sections[0].value = format!("{} m", meters(altitude));
+    }
+    for mut text in text_query.p1().iter_mut() {
+        text.sections[0].value = format!("BEST {} m", meters(best));
+    }
+}
+fn update_vertical_speed(
+    player_query: Query<&Velocity, With<PlayerLabel>>,
+    mut text_query: Query<&mut Text, With<VerticalSpeedText>>,
+) {
+    let speed = match player_query.iter().next() {
+        Some(velocity) => velocity.linvel.y / PIXELS_PER_METER,
+        None => {
+            error!("player not found");
+            return;
+        }
+    };
+    for mut text in text_query.iter_mut() {
+        text.sections[0].value = format!("{:+.1} m/s", speed);
+    }
+}
+fn update_heat_gauge(
+    player_query: Query<&PlayerCoolingTimer, With<PlayerLabel>>,
+    mut fill_query: Query<&mut Style, With<HeatFill>>,
+    mut text_query: Query<&mut Text, With<HeatText>>,
+) {
+    let used = match player_query.iter().next() {
+        Some(cooling) => cooling.get_frac_used(),
+        None => {
+            error!("player not found");
+            return;
+        }
+    };
+    for mut style in fill_query.iter_mut() {
+        style.size.height = Val::Percent(100.0 * used);
+    }
+    for mut text in text_query.iter_mut() {
+        text.sections[0].value = format!("HEAT {}%", (100.0 * used) as u32);
+    }
+}
+fn update_overheat_countdown(
+    player_query: Query<
+        (&PlayerCoolingTimer, &PlayerThrust),
+        With<PlayerLabel>,
+    >,
+    mut text_query: Query<
+        (&mut Text, &mut Visibility),
+        With<OverheatCountdown>,
+    >,
+    cooling_model: Res<CoolingModel>,
+    current_biome: Res<CurrentBiome>,
+) {
+    let (cooling, thrust) = match player_query.iter().next() {
+        Some(player) => player,
+        None => {
+            error!("player not found");
+            return;
+        }
+    };
+    let seconds = if HeatLevel::from_frac_used(cooling.get_frac_used())
+        == HeatLevel::Critical
+    {
+        cooling_model.seconds_to_overheat(
+            cooling,
+            thrust.is_thrusting(),
+            &current_biome,
+        )
+    } else {
+        None
+    };
+    for (mut text, mut visibility) in text_query.iter_mut() {
+        visibility.is_visible = seconds.is_some();
+        if let Some(seconds) = seconds {
+            let section = &mut text.sections[0];
+            section.value = format!("OVERHEAT {:.1}", seconds);
+            // flashes at the start of every second left
+            section.style.color = if seconds.fract() > 0.8 {
+                colors::COUNTDOWN_TICK_COLOR
+            } else {
+                colors::COUNTDOWN_COLOR
+            };
+        }
+    }
+}
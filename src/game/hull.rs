@@ -0,0 +1,382 @@
+use super::{
+    GameEntity, GameLabel, GameState, PlayerLabel, RunSeed, WorldEdge,
+    FUEL_MAX_Y_DIST,
+};
+use bevy::{prelude::*, sprite::MaterialMesh2dBundle};
+use bevy_rapier2d::{prelude::*, rapier::geometry::CollisionEventFlags};
+use bevy_turborand::*;
+
+pub struct HullPlugin;
+impl Plugin for HullPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ShieldAssets>()
+            .add_system_set(
+                SystemSet::on_enter(GameState::Game).with_system(
+                    insert_shield_spawner.after(GameLabel::RunSeed),
+                ),
+            )
+            .add_system_set(
+                SystemSet::on_update(GameState::Game)
+                    .with_system(damage_hull)
+                    .with_system(destroy_ship.after(GameLabel::Cooling))
+                    .with_system(blink_invulnerable)
+                    .with_system(attach_shield_bubble)
+                    .with_system(show_shield_bubble)
+                    .with_system(spawn_shield_pickup)
+                    .with_system(collect_shield)
+                    .with_system(clean_up_shields),
+            );
+    }
+}
+/// Health of the ship, hazards and hard landings wear it down
+#[derive(Component)]
+pub struct Hull {
+    health: f32,
+    max: f32,
+    /// runs after a hit, no damage is taken until it finishes
+    invulnerable: Timer,
+    /// a shield absorbs the next hit completely
+    shielded: bool,
+    /// velocity before the latest physics steps, rapier has already
+    /// resolved the impact by the time collision events are read
+    velocity_before: Vec2,
+}
+impl Hull {
+    const INVULNERABLE_TIME_SEC: f32 = 1.5;
+    /// impact speed in pixels per second the hull takes without damage
+    const SAFE_IMPACT_SPEED: f32 = 400.0;
+    /// damage per pixel per second of impact speed above the safe speed
+    const IMPACT_DAMAGE: f32 = 0.1;
+    pub fn new(max: f32) -> Self {
+        let mut invulnerable =
+            Timer::from_seconds(Self::INVULNERABLE_TIME_SEC, false);
+        invulnerable.tick(invulnerable.duration());
+        Self {
+            health: max,
+            max,
+            invulnerable,
+            shielded: false,
+            velocity_before: Vec2::ZERO,
+        }
+    }
+    /// gets health left in range 0.0 to 1.0
+    pub fn get_health_ratio_left(&self) -> f32 {
+        self.health / self.max
+    }
+    pub fn is_invulnerable(&self) -> bool {
+        !self.invulnerable.finished()
+    }
+    pub fn is_shielded(&self) -> bool {
+        self.shielded
+    }
+    pub fn is_destroyed(&self) -> bool {
+        self.health <= 0.0
+    }
+    /// damage from hitting something at `impact_speed`
+    pub fn impact_damage(impact_speed: f32) -> f32 {
+        (impact_speed - Self::SAFE_IMPACT_SPEED).max(0.0) * Self::IMPACT_DAMAGE
+    }
+    /// uses up the shield to fend off an attack that does no damage,
+    /// returns whether the attack was blocked
+    pub fn block(&mut self) -> bool {
+        if self.is_invulnerable() {
+            return true;
+        }
+        if !self.shielded {
+            return false;
+        }
+        self.shielded = false;
+        self.invulnerable.reset();
+        true
+    }
+    /// applies a hit and starts the invulnerability frames, returns the
+    /// damage actually taken
+    pub fn hit(&mut self, damage: f32) -> f32 {
+        if damage <= 0.0 || self.is_invulnerable() {
+            return 0.0;
+        }
+        self.invulnerable.reset();
+        if self.shielded {
+            self.shielded = false;
+            return 0.0;
+        }
+        let taken = damage.min(self.health);
+        self.health -= taken;
+        taken
+    }
+}
+/// Level geometry that damages the ship on any touch
+#[derive(Component)]
+pub struct Hazard {
+    pub damage: f32,
+}
+/// Pickup that gives the ship a shield
+#[derive(Component)]
+struct ShieldPickup;
+/// Bubble drawn around the ship while it is shielded
+#[derive(Component)]
+struct ShieldBubble;
+#[derive(Component)]
+struct ShieldSpawner {
+    timer: Timer,
+}
+impl ShieldSpawner {
+    const RESPAWN_TIME_SEC: f32 = 20.0;
+}
+const SHIELD_PICKUP_RADIUS: f32 = 20.0;
+/// salt for the [`RunSeed`] of shield placement
+const SHIELD_SEED: u64 = 0x5348_4945_4c44;
+/// Meshes and materials of the shield pickup and the bubble, built once
+struct ShieldAssets {
+    pickup_mesh: Handle<Mesh>,
+    pickup_material: Handle<ColorMaterial>,
+    /// unit circle scaled to the size of the ship
+    bubble_mesh: Handle<Mesh>,
+    bubble_material: Handle<ColorMaterial>,
+}
+impl FromWorld for ShieldAssets {
+    fn from_world(world: &mut World) -> Self {
+        let mut meshes = world.resource_mut::<Assets<Mesh>>();
+        let pickup_mesh =
+            meshes.add(shape::Circle::new(SHIELD_PICKUP_RADIUS).into());
+        let bubble_mesh = meshes.add(shape::Circle::new(1.0).into());
+        let mut materials = world.resource_mut::<Assets<ColorMaterial>>();
+        Self {
+            pickup_mesh,
+            pickup_material: materials
+                .add(ColorMaterial::from(colors::SHIELD_PICKUP_COLOR)),
+            bubble_mesh,
+            bubble_material: materials
+                .add(ColorMaterial::from(colors::SHIELD_BUBBLE_COLOR)),
+        }
+    }
+}
+mod colors {
+    use bevy::prelude::*;
+
+    pub const SHIELD_PICKUP_COLOR: Color = Color::rgb(0.3, 0.8, 1.0);
+    pub const SHIELD_BUBBLE_COLOR: Color = Color::rgba(0.3, 0.8, 1.0, 0.3);
+    /// ship alpha on the blinking frames while invulnerable
+    pub const INVULNERABLE_ALPHA: f32 = 0.3;
+}
+fn insert_shield_spawner(mut commands: Commands, run_seed: Res<RunSeed>) {
+    commands
+        .spawn()
+        .insert(ShieldSpawner {
+            timer: Timer::from_seconds(ShieldSpawner::RESPAWN_TIME_SEC, true),
+        })
+        .insert(RngComponent::with_seed(run_seed.derive(SHIELD_SEED)))
+        .insert(GameEntity);
+}
+/// speed the ship hit `other` with, measured along the contact normal
+fn impact_speed(
+    rapier_context: &RapierContext,
+    player: Entity,
+    other: Entity,
+    velocity: Vec2,
+) -> f32 {
+    let normal = rapier_context.contact_pair(player, other).and_then(|pair| {
+        // rapier's normal points from the first collider to the second
+        let sign = if pair.collider1() == player {
+            1.0
+        } else {
+            -1.0
+        };
+        pair.manifolds()
+            .next()
+            .map(|manifold| sign * manifold.normal())
+    });
+    match normal {
+        Some(normal) => velocity.dot(normal).max(0.0),
+        // already separated again, assume a head on hit
+        None => velocity.length(),
+    }
+}
+fn damage_hull(
+    mut player_query: Query<(Entity, &mut Hull, &Velocity), With<PlayerLabel>>,
+    hazard_query: Query<&Hazard>,
+    mut collision_events: EventReader<CollisionEvent>,
+    rapier_context: Res<RapierContext>,
+    time: Res<Time>,
+) {
+    let (player, mut hull, velocity) = match player_query.iter_mut().next() {
+        Some(player) => player,
+        None => {
+            error!("player not found");
+            return;
+        }
+    };
+    hull.invulnerable.tick(time.delta());
+    for event in collision_events.iter() {
+        let (e1, e2) = match event {
+            CollisionEvent::Started(e1, e2, flags)
+                if !flags.contains(CollisionEventFlags::SENSOR) =>
+            {
+                (*e1, *e2)
+            }
+            _ => continue,
+        };
+        let other = if e1 == player {
+            e2
+        } else if e2 == player {
+            e1
+        } else {
+            continue;
+        };
+        let speed =
+            impact_speed(&rapier_context, player, other, hull.velocity_before);
+        let mut damage = Hull::impact_damage(speed);
+        if let Ok(hazard) = hazard_query.get(other) {
+            damage += hazard.damage;
+        }
+        let taken = hull.hit(damage);
+        if taken > 0.0 {
+            info!("hull took {} damage at {} px/s", taken, speed);
+        }
+    }
+    hull.velocity_before = velocity.linvel;
+}
+fn destroy_ship(
+    player_query: Query<&Hull, With<PlayerLabel>>,
+    mut game_state: ResMut<State<GameState>>,
+) {
+    for hull in player_query.iter() {
+        if hull.is_destroyed() {
+            // overheating may have ended the run this frame already
+            let _ = game_state.set(GameState::GameOver);
+        }
+    }
+}
+fn blink_invulnerable(
+    mut player_query: Query<(&Hull, &mut TextureAtlasSprite), ()>,
+    time: Res<Time>,
+) {
+    for (hull, mut sprite) in player_query.iter_mut() {
+        let blink = hull.is_invulnerable()
+            && ((time.seconds_since_startup() * 10.0) as u64).is_multiple_of(2);
+        let alpha = if blink {
+            colors::INVULNERABLE_ALPHA
+        } else {
+            1.0
+        };
+        if sprite.color.a() != alpha {
+            sprite.color.set_a(alpha);
+        }
+    }
+}
+fn attach_shield_bubble(
+    mut commands: Commands,
+    player_query: Query<(Entity, &Collider), Added<Hull>>,
+    assets: Res<ShieldAssets>,
+) {
+    for (player, collider) in player_query.iter() {
+        let radius = collider.as_ball().map_or(10.0, |ball| ball.radius());
+        let bubble = commands
+            .spawn_bundle(MaterialMesh2dBundle {
+                mesh: assets.bubble_mesh.clone().into(),
+                material: assets.bubble_material.clone(),
+                transform: Transform::from_xyz(0.0, 0.0, 0.01)
+                    .with_scale(Vec3::new(1.5 * radius, 1.5 * radius, 1.0)),
+                visibility: Visibility { is_visible: false },
+                ..default()
+            })
+            .insert(ShieldBubble)
+            .id();
+        commands.entity(player).add_child(bubble);
+    }
+}
+fn show_shield_bubble(
+    player_query: Query<&Hull, With<PlayerLabel>>,
+    mut bubble_query: Query<&mut Visibility, With<ShieldBubble>>,
+) {
+    let shielded = player_query.iter().any(|hull| hull.is_shielded());
+    for mut visibility in bubble_query.iter_mut() {
+        visibility.is_visible = shielded;
+    }
+}
+fn spawn_shield_pickup(
+    mut commands: Commands,
+    mut spawner_query: Query<(&mut ShieldSpawner, &mut RngComponent), ()>,
+    player_query: Query<&Transform, With<PlayerLabel>>,
+    pickup_query: Query<(), With<ShieldPickup>>,
+    time: Res<Time>,
+    edge: Res<WorldEdge>,
+    assets: Res<ShieldAssets>,
+) {
+    let player_y = match player_query.iter().next() {
+        Some(transform) => transform.translation.y,
+        None => {
+            error!("player not found");
+            return;
+        }
+    };
+    for (mut spawner, mut rng) in spawner_query.iter_mut() {
+        // only one shield is out at a time
+        if !spawner.timer.tick(time.delta()).just_finished()
+            || !pickup_query.is_empty()
+        {
+            continue;
+        }
+        let x = edge.spawn_half_width() * rng.f32_normalized();
+        let y = player_y + FUEL_MAX_Y_DIST * (0.5 + 0.4 * rng.f32());
+        commands
+            .spawn_bundle(MaterialMesh2dBundle {
+                mesh: assets.pickup_mesh.clone().into(),
+                material: assets.pickup_material.clone(),
+                transform: Transform::from_xyz(x, y, 0.0),
+                ..default()
+            })
+            .insert(Collider::ball(SHIELD_PICKUP_RADIUS))
+            .insert(Sensor)
+            .insert(ShieldPickup)
+            .insert(GameEntity);
+    }
+}
+fn collect_shield(
+    mut commands: Commands,
+    mut player_query: Query<(Entity, &mut Hull), With<PlayerLabel>>,
+    pickup_query: Query<(), With<ShieldPickup>>,
+    mut collision_events: EventReader<CollisionEvent>,
+) {
+    let (player, mut hull) = match player_query.iter_mut().next() {
+        Some(player) => player,
+        None => {
+            error!("player not found");
+            return;
+        }
+    };
+    for event in collision_events.iter() {
+        if let CollisionEvent::Started(e1, e2, _) = event {
+            let pickup = if *e1 == player { *e2 } else { *e1 };
+            if (*e1 == player || *e2 == player) && pickup_query.contains(pickup)
+            {
+                hull.shielded = true;
+                commands.entity(pickup).despawn();
+            }
+        }
+    }
+}
+#[allow(clippy::type_complexity)]
+fn clean_up_shields(
+    mut commands: Commands,
+    mut p_set: ParamSet<(
+        Query<(Entity, &mut Transform), With<ShieldPickup>>,
+        Query<&Transform, With<PlayerLabel>>,
+    )>,
+    edge: Res<WorldEdge>,
+) {
+    let player_y = match p_set.p1().iter().next() {
+        Some(transform) => transform.translation.y,
+        None => {
+            error!("player not found");
+            return;
+        }
+    };
+    for (entity, mut transform) in p_set.p0().iter_mut() {
+        if (transform.translation.y - player_y).abs() > FUEL_MAX_Y_DIST
+            || !edge.contain(&mut transform.translation)
+        {
+            commands.entity(entity).despawn_recursive();
+        }
+    }
+}
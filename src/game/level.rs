@@ -0,0 +1,358 @@
+use super::{
+    checkpoint::spawn_checkpoint_platform,
+    fields::{spawn_force_field, FieldAssets},
+    Checkpoints, FieldKind, GameEntity, GameState, Hazard, PlayerLabel,
+    RunSeed, CHECKPOINT_INTERVAL, FUEL_MAX_Y_DIST, WORLD_HALF_WIDTH,
+};
+use bevy::{prelude::*, sprite::MaterialMesh2dBundle, utils::HashMap};
+use bevy_rapier2d::prelude::*;
+use bevy_turborand::prelude::*;
+
+pub struct LevelPlugin;
+impl Plugin for LevelPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(LoadedChunks::default())
+            .init_resource::<LevelAssets>()
+            .add_system_set(
+                SystemSet::on_update(GameState::Game)
+                    .with_system(stream_chunks),
+            )
+            .add_system_set(
+                SystemSet::on_exit(GameState::Game).with_system(clear_chunks),
+            );
+    }
+}
+/// Height of one vertical segment of the climb
+pub const CHUNK_HEIGHT: f32 = 1000.0;
+/// Kinds of vertical segments the climb is built from
+#[derive(Clone, Copy, Eq, PartialEq, Debug)]
+pub enum ChunkTemplate {
+    OpenSky,
+    Corridor,
+    PlatformLadder,
+    AsteroidBelt,
+}
+impl ChunkTemplate {
+    /// Relative chance of each template being picked at the given chunk
+    /// index. Low chunks are mostly open while higher ones get crowded.
+    fn weights(index: i32) -> [(ChunkTemplate, f32); 4] {
+        let difficulty = (index as f32 / 20.0).min(1.0);
+        [
+            (ChunkTemplate::OpenSky, 3.0 - 2.0 * difficulty),
+            (ChunkTemplate::PlatformLadder, 1.0),
+            (ChunkTemplate::Corridor, 2.0 * difficulty),
+            (ChunkTemplate::AsteroidBelt, 3.0 * difficulty),
+        ]
+    }
+    /// picks the template for a chunk, the start of the climb is always open
+    pub fn choose(index: i32, rng: &Rng) -> Self {
+        if index <= 0 {
+            return ChunkTemplate::OpenSky;
+        }
+        let weights = Self::weights(index);
+        let total: f32 = weights.iter().map(|(_, weight)| weight).sum();
+        let mut roll = rng.f32() * total;
+        for (template, weight) in weights {
+            if roll < weight {
+                return template;
+            }
+            roll -= weight;
+        }
+        ChunkTemplate::OpenSky
+    }
+    /// lays out the obstacles of a chunk relative to its bottom center
+    fn obstacles(&self, rng: &Rng) -> Vec<Obstacle> {
+        match self {
+            ChunkTemplate::OpenSky => Vec::new(),
+            ChunkTemplate::Corridor => {
+                const GAP: f32 = 250.0;
+                const HEIGHT: f32 = 600.0;
+                let gap_center =
+                    (WORLD_HALF_WIDTH - GAP) * rng.f32_normalized();
+                let y = 0.5 * CHUNK_HEIGHT;
+                let left_width = gap_center - 0.5 * GAP + WORLD_HALF_WIDTH;
+                let right_width = WORLD_HALF_WIDTH - gap_center - 0.5 * GAP;
+                vec![
+                    Obstacle::Block {
+                        center: Vec2::new(
+                            -WORLD_HALF_WIDTH + 0.5 * left_width,
+                            y,
+                        ),
+                        size: Vec2::new(left_width, HEIGHT),
+                    },
+                    Obstacle::Block {
+                        center: Vec2::new(
+                            WORLD_HALF_WIDTH - 0.5 * right_width,
+                            y,
+                        ),
+                        size: Vec2::new(right_width, HEIGHT),
+                    },
+                ]
+            }
+            ChunkTemplate::PlatformLadder => {
+                const STEP: f32 = 250.0;
+                const SIZE: Vec2 = Vec2::new(250.0, 20.0);
+                let mut side = if rng.bool() { 1.0 } else { -1.0 };
+                let mut obstacles = Vec::new();
+                let mut y = 0.5 * STEP;
+                while y < CHUNK_HEIGHT {
+                    let x = side * (0.5 * WORLD_HALF_WIDTH)
+                        + 50.0 * rng.f32_normalized();
+                    obstacles.push(Obstacle::Block {
+                        center: Vec2::new(x, y),
+                        size: SIZE,
+                    });
+                    side = -side;
+                    y += STEP;
+                }
+                obstacles
+            }
+            ChunkTemplate::AsteroidBelt => {
+                let count = rng.usize(5..10);
+                (0..count)
+                    .map(|_| Obstacle::Asteroid {
+                        center: Vec2::new(
+                            WORLD_HALF_WIDTH * rng.f32_normalized(),
+                            CHUNK_HEIGHT * (0.3 + 0.4 * rng.f32()),
+                        ),
+                        radius: 20.0 + 40.0 * rng.f32(),
+                    })
+                    .collect()
+            }
+        }
+    }
+    /// lays out the force fields of a chunk, rolled after the obstacles so
+    /// the obstacles of a seed stay the same
+    fn fields(&self, index: i32, rng: &Rng) -> Vec<Obstacle> {
+        // the start of the climb is left calm
+        if index <= 0 {
+            return Vec::new();
+        }
+        let y = CHUNK_HEIGHT * (0.2 + 0.6 * rng.f32());
+        match self {
+            ChunkTemplate::OpenSky | ChunkTemplate::PlatformLadder
+                if rng.f32() < 0.5 =>
+            {
+                let direction = if rng.bool() { 1.0 } else { -1.0 };
+                vec![Obstacle::Field {
+                    center: Vec2::new(0.0, y),
+                    size: Vec2::new(2.0 * WORLD_HALF_WIDTH, 200.0),
+                    kind: FieldKind::Wind {
+                        accel: direction * (300.0 + 300.0 * rng.f32()),
+                    },
+                }]
+            }
+            ChunkTemplate::OpenSky | ChunkTemplate::Corridor => {
+                vec![Obstacle::Field {
+                    center: Vec2::new(
+                        (WORLD_HALF_WIDTH - 150.0) * rng.f32_normalized(),
+                        y,
+                    ),
+                    size: Vec2::new(200.0, 500.0),
+                    kind: FieldKind::Updraft {
+                        accel: 600.0 + 300.0 * rng.f32(),
+                    },
+                }]
+            }
+            ChunkTemplate::AsteroidBelt if rng.f32() < 0.4 => {
+                const RADIUS: f32 = 250.0;
+                vec![Obstacle::Field {
+                    center: Vec2::new(
+                        (WORLD_HALF_WIDTH - RADIUS) * rng.f32_normalized(),
+                        y,
+                    ),
+                    size: Vec2::splat(2.0 * RADIUS),
+                    kind: FieldKind::GravityWell {
+                        accel: 1200.0,
+                        radius: RADIUS,
+                    },
+                }]
+            }
+            _ => Vec::new(),
+        }
+    }
+}
+/// checkpoint platform of the chunk at `index` if one of the fixed
+/// checkpoint altitudes falls inside it
+fn checkpoint(index: i32) -> Option<Obstacle> {
+    let bottom = index as f32 * CHUNK_HEIGHT;
+    let altitude = (bottom / CHECKPOINT_INTERVAL).ceil() * CHECKPOINT_INTERVAL;
+    (index > 0 && altitude < bottom + CHUNK_HEIGHT).then_some(
+        Obstacle::Checkpoint {
+            center: Vec2::new(0.0, altitude - bottom),
+            altitude,
+        },
+    )
+}
+/// A piece of a chunk
+enum Obstacle {
+    Block {
+        center: Vec2,
+        size: Vec2,
+    },
+    Asteroid {
+        center: Vec2,
+        radius: f32,
+    },
+    /// a volume that pushes the ship instead of blocking it
+    Field {
+        center: Vec2,
+        size: Vec2,
+        kind: FieldKind,
+    },
+    /// a landing platform that saves the run
+    Checkpoint {
+        center: Vec2,
+        altitude: f32,
+    },
+}
+/// hull damage from touching an asteroid, on top of the impact damage
+const ASTEROID_DAMAGE: f32 = 20.0;
+/// Marks static level geometry
+#[derive(Component)]
+pub struct LevelObstacle;
+/// Root of the obstacles of one chunk
+#[derive(Component)]
+pub struct Chunk;
+/// Chunks currently spawned keyed by their index
+#[derive(Default)]
+pub struct LoadedChunks {
+    chunks: HashMap<i32, Entity>,
+}
+/// Mesh and material shared by every asteroid, a unit circle scaled to the
+/// radius of each one
+struct LevelAssets {
+    asteroid_mesh: Handle<Mesh>,
+    asteroid_material: Handle<ColorMaterial>,
+}
+impl FromWorld for LevelAssets {
+    fn from_world(world: &mut World) -> Self {
+        let asteroid_mesh = world
+            .resource_mut::<Assets<Mesh>>()
+            .add(shape::Circle::new(1.0).into());
+        let asteroid_material = world
+            .resource_mut::<Assets<ColorMaterial>>()
+            .add(ColorMaterial::from(colors::ASTEROID_COLOR));
+        Self {
+            asteroid_mesh,
+            asteroid_material,
+        }
+    }
+}
+mod colors {
+    use bevy::prelude::*;
+
+    pub const BLOCK_COLOR: Color = Color::rgb(0.45, 0.45, 0.5);
+    pub const ASTEROID_COLOR: Color = Color::rgb(0.5, 0.4, 0.3);
+}
+/// Spawns chunks that come within [`FUEL_MAX_Y_DIST`] of the player and
+/// removes the ones that fall out of it, the same window pickups live in
+fn stream_chunks(
+    mut commands: Commands,
+    mut loaded: ResMut<LoadedChunks>,
+    player_query: Query<&Transform, With<PlayerLabel>>,
+    run_seed: Res<RunSeed>,
+    checkpoints: Res<Checkpoints>,
+    assets: Res<LevelAssets>,
+    field_assets: Res<FieldAssets>,
+) {
+    let player_y = match player_query.iter().next() {
+        Some(transform) => transform.translation.y,
+        None => return,
+    };
+    let lowest = ((player_y - FUEL_MAX_Y_DIST) / CHUNK_HEIGHT).floor() as i32;
+    let highest = ((player_y + FUEL_MAX_Y_DIST) / CHUNK_HEIGHT).floor() as i32;
+    loaded.chunks.retain(|index, entity| {
+        let keep = (lowest..=highest).contains(index);
+        if !keep {
+            commands.entity(*entity).despawn_recursive();
+        }
+        keep
+    });
+    for index in lowest.max(0)..=highest {
+        if loaded.chunks.contains_key(&index) {
+            continue;
+        }
+        let rng = Rng::with_seed(run_seed.derive(index as u64));
+        let template = ChunkTemplate::choose(index, &rng);
+        let entity = commands
+            .spawn_bundle(SpatialBundle::from_transform(
+                Transform::from_translation(Vec3::new(
+                    0.0,
+                    index as f32 * CHUNK_HEIGHT,
+                    0.0,
+                )),
+            ))
+            .insert(Chunk)
+            .insert(GameEntity)
+            .with_children(|parent| {
+                let obstacles = template.obstacles(&rng);
+                let fields = template.fields(index, &rng);
+                for obstacle in
+                    obstacles.into_iter().chain(fields).chain(checkpoint(index))
+                {
+                    spawn_obstacle(
+                        parent,
+                        obstacle,
+                        &checkpoints,
+                        &assets,
+                        &field_assets,
+                    );
+                }
+            })
+            .id();
+        loaded.chunks.insert(index, entity);
+    }
+}
+fn spawn_obstacle(
+    parent: &mut ChildBuilder,
+    obstacle: Obstacle,
+    checkpoints: &Checkpoints,
+    assets: &LevelAssets,
+    field_assets: &FieldAssets,
+) {
+    match obstacle {
+        Obstacle::Block { center, size } => {
+            parent
+                .spawn_bundle(SpriteBundle {
+                    sprite: Sprite {
+                        color: colors::BLOCK_COLOR,
+                        custom_size: Some(size),
+                        ..default()
+                    },
+                    transform: Transform::from_translation(center.extend(0.0)),
+                    ..default()
+                })
+                .insert(Collider::cuboid(0.5 * size.x, 0.5 * size.y))
+                .insert(LevelObstacle);
+        }
+        Obstacle::Asteroid { center, radius } => {
+            parent
+                .spawn_bundle(MaterialMesh2dBundle {
+                    mesh: assets.asteroid_mesh.clone().into(),
+                    material: assets.asteroid_material.clone(),
+                    // rapier scales the collider along with the mesh
+                    transform: Transform::from_translation(center.extend(0.0))
+                        .with_scale(Vec3::new(radius, radius, 1.0)),
+                    ..default()
+                })
+                .insert(Collider::ball(1.0))
+                .insert(Hazard {
+                    damage: ASTEROID_DAMAGE,
+                })
+                .insert(LevelObstacle);
+        }
+        Obstacle::Field { center, size, kind } => {
+            spawn_force_field(parent, center, size, kind, field_assets);
+        }
+        Obstacle::Checkpoint { center, altitude } => {
+            let reached = checkpoints
+                .last_altitude()
+                .is_some_and(|last| last >= altitude);
+            spawn_checkpoint_platform(parent, center, altitude, reached);
+        }
+    }
+}
+fn clear_chunks(mut loaded: ResMut<LoadedChunks>) {
+    loaded.chunks.clear();
+}
@@ -0,0 +1,217 @@
+use super::{
+    ui::{button_clicked, colors, spawn_button, spawn_sized_button},
+    CoolingModel, DailyChallenge, FlightModel, GameMode, GameState, MaxScore,
+    MinimapMode, PlayerProfile, WorldEdge,
+};
+use crate::loading::FontAssets;
+use bevy::prelude::*;
+
+pub struct MenuPlugin;
+impl Plugin for MenuPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_system_set(
+            SystemSet::on_enter(GameState::MainMenu)
+                .with_system(spawn_main_menu),
+        )
+        .add_system_set(
+            SystemSet::on_update(GameState::MainMenu)
+                .with_system(menu_buttons)
+                .with_system(setting_buttons),
+        )
+        .add_system_set(
+            SystemSet::on_exit(GameState::MainMenu)
+                .with_system(clear_main_menu),
+        );
+    }
+}
+#[derive(Component)]
+struct MainMenuEntity;
+/// Entries on the main menu, each starts a run in the given mode
+#[derive(Component, Clone, Copy)]
+enum MenuButton {
+    Play,
+    DailyChallenge,
+    RuleShift,
+    Tutorial,
+}
+/// Settings on the main menu, clicking cycles through the options
+#[derive(Component, Clone, Copy)]
+enum SettingButton {
+    WorldEdge,
+    FlightModel,
+    Cooling,
+    Skin,
+    Minimap,
+}
+impl SettingButton {
+    const ALL: [SettingButton; 5] = [
+        SettingButton::WorldEdge,
+        SettingButton::FlightModel,
+        SettingButton::Cooling,
+        SettingButton::Skin,
+        SettingButton::Minimap,
+    ];
+    fn label(
+        &self,
+        edge: &WorldEdge,
+        flight_model: &FlightModel,
+        cooling_model: &CoolingModel,
+        profile: &PlayerProfile,
+        minimap: &MinimapMode,
+    ) -> String {
+        match self {
+            SettingButton::WorldEdge => format!("Edges: {}", edge.name()),
+            SettingButton::FlightModel => {
+                format!("Flight: {}", flight_model.name())
+            }
+            SettingButton::Cooling => {
+                format!("Cooling: {}", cooling_model.name())
+            }
+            SettingButton::Skin => {
+                format!("Skin: {}", profile.get_skin().name())
+            }
+            SettingButton::Minimap => format!("Minimap: {}", minimap.name()),
+        }
+    }
+}
+#[allow(clippy::too_many_arguments)]
+fn spawn_main_menu(
+    mut commands: Commands,
+    fonts: Res<FontAssets>,
+    max_score: Res<MaxScore>,
+    daily: Res<DailyChallenge>,
+    edge: Res<WorldEdge>,
+    flight_model: Res<FlightModel>,
+    cooling_model: Res<CoolingModel>,
+    profile: Res<PlayerProfile>,
+    minimap: Res<MinimapMode>,
+) {
+    commands
+        .spawn_bundle(Camera2dBundle::default())
+        .insert(MainMenuEntity);
+    commands
+        .spawn_bundle(NodeBundle {
+            style: Style {
+                margin: UiRect::all(Val::Auto),
+                flex_direction: FlexDirection::ColumnReverse,
+                align_items: AlignItems::Center,
+                ..default()
+            },
+            color: colors::BUTTON_NORMAL_COLOR.into(),
+            ..default()
+        })
+        .insert(MainMenuEntity)
+        .with_children(|parent| {
+            parent.spawn_bundle(TextBundle::from_section(
+                format!(
+                    "HIGH SCORE: {} ({})",
+                    max_score.score.get_score(),
+                    max_score.ship.name()
+                ),
+                TextStyle {
+                    font: fonts.silkscreen.clone(),
+                    font_size: colors::SCORE_SIZE,
+                    color: Color::BLACK,
+                },
+            ));
+            spawn_button(parent, &fonts, "Play", MenuButton::Play);
+            spawn_button(parent, &fonts, "Rule Shift", MenuButton::RuleShift);
+            spawn_button(parent, &fonts, "Tutorial", MenuButton::Tutorial);
+            if daily.can_attempt() {
+                spawn_button(
+                    parent,
+                    &fonts,
+                    &format!("Daily {}", daily.date()),
+                    MenuButton::DailyChallenge,
+                );
+            } else {
+                parent.spawn_bundle(TextBundle::from_section(
+                    format!("DAILY DONE: {}", daily.get_best().get_score()),
+                    TextStyle {
+                        font: fonts.silkscreen.clone(),
+                        font_size: colors::SCORE_SIZE,
+                        color: Color::BLACK,
+                    },
+                ));
+            }
+            for setting in SettingButton::ALL {
+                spawn_sized_button(
+                    parent,
+                    &fonts,
+                    &setting.label(
+                        &edge,
+                        &flight_model,
+                        &cooling_model,
+                        &profile,
+                        &minimap,
+                    ),
+                    colors::SCORE_SIZE,
+                    setting,
+                );
+            }
+        });
+}
+fn menu_buttons(
+    mut button_query: Query<(&mut UiColor, &Interaction, &MenuButton), ()>,
+    mut game_state: ResMut<State<GameState>>,
+    mut game_mode: ResMut<GameMode>,
+    profile: Res<PlayerProfile>,
+) {
+    for (mut color, interaction, button) in button_query.iter_mut() {
+        if button_clicked(&mut color, interaction) {
+            *game_mode = match button {
+                // the very first run teaches the basics
+                MenuButton::Play if !profile.has_seen_tutorial() => {
+                    GameMode::Tutorial
+                }
+                MenuButton::Play => GameMode::Classic,
+                MenuButton::Tutorial => GameMode::Tutorial,
+                MenuButton::DailyChallenge => GameMode::DailyChallenge,
+                MenuButton::RuleShift => GameMode::RuleShift,
+            };
+            game_state.set(GameState::ShipSelect).unwrap();
+        }
+    }
+}
+fn setting_buttons(
+    mut button_query: Query<
+        (&mut UiColor, &Interaction, &Children, &SettingButton),
+        Changed<Interaction>,
+    >,
+    mut text_query: Query<&mut Text>,
+    mut edge: ResMut<WorldEdge>,
+    mut flight_model: ResMut<FlightModel>,
+    mut cooling_model: ResMut<CoolingModel>,
+    mut profile: ResMut<PlayerProfile>,
+    mut minimap: ResMut<MinimapMode>,
+) {
+    for (mut color, interaction, children, setting) in button_query.iter_mut() {
+        if !button_clicked(&mut color, interaction) {
+            continue;
+        }
+        match setting {
+            SettingButton::WorldEdge => *edge = edge.next(),
+            SettingButton::FlightModel => *flight_model = flight_model.next(),
+            SettingButton::Cooling => *cooling_model = cooling_model.next(),
+            SettingButton::Skin => profile.next_skin(),
+            SettingButton::Minimap => *minimap = minimap.next(),
+        }
+        if let Ok(mut text) = text_query.get_mut(children[0]) {
+            text.sections[0].value = setting.label(
+                &edge,
+                &flight_model,
+                &cooling_model,
+                &profile,
+                &minimap,
+            );
+        }
+    }
+}
+fn clear_main_menu(
+    mut commands: Commands,
+    entity_query: Query<Entity, With<MainMenuEntity>>,
+) {
+    for entity in entity_query.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+}
@@ -0,0 +1,154 @@
+use super::{GameMode, GameState, PlayerCoolingTimer};
+use bevy::prelude::*;
+use bevy_turborand::*;
+
+pub struct ModifierPlugin;
+impl Plugin for ModifierPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(ActiveModifiers::default())
+            .add_system_set(
+                SystemSet::on_enter(GameState::Game)
+                    .with_system(reset_modifiers),
+            )
+            .add_system_set(
+                SystemSet::on_update(GameState::Game)
+                    .with_system(shift_modifiers),
+            )
+            .add_system_set(
+                SystemSet::on_exit(GameState::Game)
+                    .with_system(clear_modifiers),
+            );
+    }
+}
+/// Rule changes applied during a rule shift run
+#[derive(Clone, Copy, Eq, PartialEq, Debug)]
+pub enum Modifier {
+    GravityFlip,
+    HalfThrust,
+    PickupDrift,
+    CameraZoomOut,
+}
+impl Modifier {
+    pub const ALL: [Modifier; 4] = [
+        Modifier::GravityFlip,
+        Modifier::HalfThrust,
+        Modifier::PickupDrift,
+        Modifier::CameraZoomOut,
+    ];
+    /// text shown on the hud when the modifier is applied
+    pub fn name(&self) -> &'static str {
+        match self {
+            Modifier::GravityFlip => "GRAVITY FLIP",
+            Modifier::HalfThrust => "HALF THRUST",
+            Modifier::PickupDrift => "PICKUP DRIFT",
+            Modifier::CameraZoomOut => "ZOOM OUT",
+        }
+    }
+}
+/// Registry of the modifiers currently in effect. Gameplay systems query it
+/// instead of checking the game mode themselves.
+pub struct ActiveModifiers {
+    active: Option<Modifier>,
+    /// when enabled a new modifier is picked every time this finishes
+    timer: Timer,
+    enabled: bool,
+    /// sideways direction pickups drift in, either -1.0 or 1.0
+    drift_direction: f32,
+}
+impl ActiveModifiers {
+    /// speed pickups drift with in pixels per second
+    pub const DRIFT_SPEED: f32 = 60.0;
+    /// camera scale while zoomed out
+    pub const ZOOM_OUT_SCALE: f32 = 2.0;
+    pub fn is_active(&self, modifier: Modifier) -> bool {
+        self.active == Some(modifier)
+    }
+    pub fn get_active(&self) -> Option<Modifier> {
+        self.active
+    }
+    /// seconds until the next modifier is applied
+    pub fn get_time_left(&self) -> f32 {
+        (self.timer.duration() - self.timer.elapsed()).as_secs_f32()
+    }
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+    /// multiplier for gravity acting on the player
+    pub fn gravity_scale(&self) -> f32 {
+        if self.is_active(Modifier::GravityFlip) {
+            -1.0
+        } else {
+            1.0
+        }
+    }
+    /// multiplier for the player's thrust
+    pub fn thrust_scale(&self) -> f32 {
+        if self.is_active(Modifier::HalfThrust) {
+            0.5
+        } else {
+            1.0
+        }
+    }
+    /// sideways velocity of pickups in pixels per second
+    pub fn pickup_drift(&self) -> f32 {
+        if self.is_active(Modifier::PickupDrift) {
+            self.drift_direction * Self::DRIFT_SPEED
+        } else {
+            0.0
+        }
+    }
+    /// multiplier for the camera's orthographic scale
+    pub fn camera_scale(&self) -> f32 {
+        if self.is_active(Modifier::CameraZoomOut) {
+            Self::ZOOM_OUT_SCALE
+        } else {
+            1.0
+        }
+    }
+}
+impl Default for ActiveModifiers {
+    fn default() -> Self {
+        Self {
+            active: None,
+            timer: Timer::new(PlayerCoolingTimer::COOLING_TIME, true),
+            enabled: false,
+            drift_direction: 1.0,
+        }
+    }
+}
+fn reset_modifiers(
+    mut modifiers: ResMut<ActiveModifiers>,
+    game_mode: Res<GameMode>,
+) {
+    *modifiers = ActiveModifiers {
+        enabled: *game_mode == GameMode::RuleShift,
+        ..default()
+    };
+}
+/// picks a new modifier every [`PlayerCoolingTimer::COOLING_TIME`], never
+/// the same one twice in a row
+fn shift_modifiers(
+    mut modifiers: ResMut<ActiveModifiers>,
+    mut global_rng: ResMut<GlobalRng>,
+    time: Res<Time>,
+) {
+    if !modifiers.enabled {
+        return;
+    }
+    modifiers.timer.tick(time.delta());
+    if !modifiers.timer.just_finished() {
+        return;
+    }
+    let choices = Modifier::ALL
+        .iter()
+        .filter(|m| Some(**m) != modifiers.active)
+        .copied()
+        .collect::<Vec<_>>();
+    let next = choices[global_rng.usize(0..choices.len())];
+    info!("applying modifier {:?}", next);
+    modifiers.active = Some(next);
+    modifiers.drift_direction = if global_rng.bool() { 1.0 } else { -1.0 };
+}
+fn clear_modifiers(mut modifiers: ResMut<ActiveModifiers>) {
+    *modifiers = ActiveModifiers::default();
+}
@@ -0,0 +1,208 @@
+use super::{
+    lerp_color, GameEntity, GameState, PlayerFuel, PlayerLabel, PlayerThrust,
+};
+use bevy::prelude::*;
+use bevy_rapier2d::prelude::*;
+use bevy_turborand::*;
+
+/// Lightweight particles simulated on the cpu, the wasm build can not rely
+/// on compute shaders
+pub struct ParticlePlugin;
+impl Plugin for ParticlePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_system_set(
+            SystemSet::on_update(GameState::Game)
+                .with_system(emit_exhaust)
+                .with_system(emit_trail)
+                .with_system(update_particles),
+        );
+    }
+}
+/// Upper bound on live particles, emitters stop spawning past it
+pub const MAX_PARTICLES: usize = 1000;
+/// Between the background and the world
+const PARTICLE_Z: f32 = -0.005;
+/// Everything needed to spawn a particle
+#[derive(Clone, Copy)]
+pub struct ParticleSpec {
+    pub position: Vec2,
+    pub velocity: Vec2,
+    /// seconds the particle lives for
+    pub lifetime: f32,
+    pub start_color: Color,
+    pub end_color: Color,
+    pub start_size: f32,
+    pub end_size: f32,
+}
+#[derive(Component)]
+pub struct Particle {
+    spec: ParticleSpec,
+    age: f32,
+}
+pub fn spawn_particle(commands: &mut Commands, spec: ParticleSpec) {
+    commands
+        .spawn_bundle(SpriteBundle {
+            sprite: Sprite {
+                color: spec.start_color,
+                custom_size: Some(Vec2::splat(spec.start_size)),
+                ..default()
+            },
+            transform: Transform::from_translation(
+                spec.position.extend(PARTICLE_Z),
+            ),
+            ..default()
+        })
+        .insert(Particle { spec, age: 0.0 })
+        .insert(GameEntity);
+}
+/// Emits exhaust opposite to the thrust, more fuel burned means more and
+/// faster particles
+#[derive(Component)]
+pub struct ExhaustEmitter {
+    /// particles per unit of fuel burned
+    pub particles_per_fuel: f32,
+    /// fractional particles carried over between frames
+    accumulator: f32,
+}
+impl Default for ExhaustEmitter {
+    fn default() -> Self {
+        Self {
+            particles_per_fuel: 0.05,
+            accumulator: 0.0,
+        }
+    }
+}
+/// Leaves fading puffs behind a moving entity
+#[derive(Component)]
+pub struct TrailEmitter {
+    timer: Timer,
+    /// speed below which no trail is left
+    pub min_speed: f32,
+}
+impl Default for TrailEmitter {
+    fn default() -> Self {
+        Self {
+            timer: Timer::from_seconds(0.03, true),
+            min_speed: 100.0,
+        }
+    }
+}
+mod colors {
+    use bevy::prelude::*;
+
+    pub const EXHAUST_START: Color = Color::rgba(1.0, 0.9, 0.3, 1.0);
+    pub const EXHAUST_END: Color = Color::rgba(1.0, 0.2, 0.0, 0.0);
+    pub const TRAIL_START: Color = Color::rgba(0.8, 0.8, 0.8, 0.4);
+    pub const TRAIL_END: Color = Color::rgba(0.8, 0.8, 0.8, 0.0);
+}
+fn emit_exhaust(
+    mut commands: Commands,
+    mut player_query: Query<
+        (
+            &Transform,
+            &Velocity,
+            &PlayerThrust,
+            &PlayerFuel,
+            &mut ExhaustEmitter,
+        ),
+        With<PlayerLabel>,
+    >,
+    particle_query: Query<(), With<Particle>>,
+    mut global_rng: ResMut<GlobalRng>,
+    time: Res<Time>,
+) {
+    let mut budget = MAX_PARTICLES.saturating_sub(particle_query.iter().len());
+    for (transform, velocity, thrust, fuel, mut emitter) in
+        player_query.iter_mut()
+    {
+        // no fuel means no flame, the clearest sign the tank is empty
+        if fuel.get_fuel() <= 0.0 || !thrust.is_thrusting() {
+            emitter.accumulator = 0.0;
+            continue;
+        }
+        emitter.accumulator += thrust.fuel_burned * emitter.particles_per_fuel;
+        let count = emitter.accumulator.floor();
+        emitter.accumulator -= count;
+        // fuel burned per second is the throttle
+        let intensity =
+            (thrust.fuel_burned / time.delta_seconds().max(1e-4)) / 1000.0;
+        let position = transform.translation.truncate();
+        for _ in 0..(count as usize).min(budget) {
+            let spread = Vec2::new(
+                global_rng.f32_normalized(),
+                global_rng.f32_normalized(),
+            ) * 60.0;
+            spawn_particle(
+                &mut commands,
+                ParticleSpec {
+                    position: position - thrust.direction * 20.0,
+                    velocity: velocity.linvel
+                        - thrust.direction * (150.0 + 250.0 * intensity)
+                        + spread,
+                    lifetime: 0.2 + 0.3 * global_rng.f32(),
+                    start_color: colors::EXHAUST_START,
+                    end_color: colors::EXHAUST_END,
+                    start_size: 6.0 + 4.0 * intensity,
+                    end_size: 2.0,
+                },
+            );
+            budget -= 1;
+        }
+    }
+}
+fn emit_trail(
+    mut commands: Commands,
+    mut emitter_query: Query<(&Transform, &Velocity, &mut TrailEmitter), ()>,
+    particle_query: Query<(), With<Particle>>,
+    time: Res<Time>,
+) {
+    if particle_query.iter().len() >= MAX_PARTICLES {
+        return;
+    }
+    for (transform, velocity, mut emitter) in emitter_query.iter_mut() {
+        emitter.timer.tick(time.delta());
+        if !emitter.timer.just_finished()
+            || velocity.linvel.length() < emitter.min_speed
+        {
+            continue;
+        }
+        spawn_particle(
+            &mut commands,
+            ParticleSpec {
+                position: transform.translation.truncate(),
+                velocity: Vec2::ZERO,
+                lifetime: 0.6,
+                start_color: colors::TRAIL_START,
+                end_color: colors::TRAIL_END,
+                start_size: 10.0,
+                end_size: 4.0,
+            },
+        );
+    }
+}
+fn update_particles(
+    mut commands: Commands,
+    mut particle_query: Query<
+        (Entity, &mut Particle, &mut Transform, &mut Sprite),
+        (),
+    >,
+    time: Res<Time>,
+) {
+    let delta = time.delta_seconds();
+    for (entity, mut particle, mut transform, mut sprite) in
+        particle_query.iter_mut()
+    {
+        particle.age += delta;
+        if particle.age >= particle.spec.lifetime {
+            commands.entity(entity).despawn();
+            continue;
+        }
+        let spec = particle.spec;
+        let t = particle.age / spec.lifetime;
+        transform.translation += (spec.velocity * delta).extend(0.0);
+        sprite.color = lerp_color(spec.start_color, spec.end_color, t);
+        sprite.custom_size = Some(Vec2::splat(
+            spec.start_size + (spec.end_size - spec.start_size) * t,
+        ));
+    }
+}
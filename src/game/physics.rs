@@ -0,0 +1,156 @@
+use bevy::{
+    ecs::event::Events,
+    prelude::*,
+    time::{FixedTimestep, FixedTimesteps},
+};
+use bevy_rapier2d::{plugin::systems, prelude::*};
+
+/// Runs rapier at a fixed rate and interpolates transforms in between so
+/// the same input gives the same trajectory at any frame rate
+pub struct PhysicsPlugin;
+impl Plugin for PhysicsPlugin {
+    fn build(&self, app: &mut App) {
+        type Rapier = RapierPhysicsPlugin<NoUserData>;
+        app.insert_resource(RapierConfiguration {
+            // every run of the fixed schedule is exactly one step
+            timestep_mode: TimestepMode::Fixed {
+                dt: PHYSICS_DT,
+                substeps: 1,
+            },
+            ..default()
+        })
+        .add_plugin(
+            Rapier::pixels_per_meter(PIXELS_PER_METER)
+                .with_default_system_setup(false),
+        )
+        .add_stage_after(
+            CoreStage::Update,
+            FixedPhysicsStage::Step,
+            Schedule::default()
+                .with_run_criteria(
+                    FixedTimestep::step(PHYSICS_DT as f64)
+                        .with_label(PHYSICS_TIMESTEP),
+                )
+                .with_stage(FixedPhysicsStage::Flight, SystemStage::parallel())
+                .with_stage(
+                    PhysicsStages::SyncBackend,
+                    SystemStage::parallel().with_system_set(
+                        Rapier::get_systems(PhysicsStages::SyncBackend),
+                    ),
+                )
+                .with_stage(
+                    PhysicsStages::StepSimulation,
+                    SystemStage::parallel()
+                        .with_system(systems::step_simulation::<NoUserData>),
+                )
+                .with_stage(
+                    PhysicsStages::Writeback,
+                    SystemStage::parallel()
+                        .with_system_set(Rapier::get_systems(
+                            PhysicsStages::Writeback,
+                        ))
+                        .with_system(
+                            record_poses.after(systems::writeback_rigid_bodies),
+                        ),
+                ),
+        )
+        .add_stage_before(
+            FixedPhysicsStage::Step,
+            FixedPhysicsStage::Restore,
+            SystemStage::parallel().with_system(restore_poses),
+        )
+        .add_stage_after(
+            FixedPhysicsStage::Step,
+            FixedPhysicsStage::Interpolate,
+            SystemStage::parallel().with_system(interpolate_poses),
+        )
+        .add_stage_before(
+            CoreStage::Last,
+            PhysicsStages::DetectDespawn,
+            SystemStage::parallel().with_system_set(Rapier::get_systems(
+                PhysicsStages::DetectDespawn,
+            )),
+        )
+        // several steps can run in one frame, the events have to last until
+        // the game reads them in the next update
+        .add_system_to_stage(
+            CoreStage::First,
+            Events::<CollisionEvent>::update_system,
+        )
+        .add_system_to_stage(
+            CoreStage::First,
+            Events::<ContactForceEvent>::update_system,
+        );
+    }
+}
+/// Scale between world units and the meters rapier simulates in
+pub const PIXELS_PER_METER: f32 = 100.0;
+/// Length of one physics step in seconds
+pub const PHYSICS_DT: f32 = 1.0 / 60.0;
+/// label of the fixed timestep the physics runs on
+const PHYSICS_TIMESTEP: &str = "physics";
+#[derive(StageLabel, Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum FixedPhysicsStage {
+    /// puts interpolated bodies back where the last step left them
+    Restore,
+    /// schedule that runs once for every physics step
+    Step,
+    /// runs before rapier in every step, systems that push bodies with
+    /// forces belong here
+    Flight,
+    /// draws interpolated bodies between their last two steps
+    Interpolate,
+}
+/// Draws a rigid body between its poses of the last two physics steps
+/// instead of jumping from step to step
+#[derive(Component, Default)]
+pub struct PoseInterpolation {
+    previous: Option<(Vec3, Quat)>,
+    current: Option<(Vec3, Quat)>,
+    /// transform written for drawing, anything else was set by the game
+    rendered: Option<Transform>,
+}
+fn restore_poses(mut query: Query<(&mut Transform, &mut PoseInterpolation)>) {
+    for (mut transform, mut interpolation) in query.iter_mut() {
+        match (interpolation.current, interpolation.rendered) {
+            (Some((translation, rotation)), Some(rendered))
+                if *transform == rendered =>
+            {
+                transform.translation = translation;
+                transform.rotation = rotation;
+            }
+            // moved by the game, rapier picks up the new transform and
+            // there is nothing to interpolate from until the next step
+            (_, Some(_)) => {
+                interpolation.previous = None;
+                interpolation.current = None;
+            }
+            _ => (),
+        }
+        interpolation.rendered = None;
+    }
+}
+fn record_poses(mut query: Query<(&Transform, &mut PoseInterpolation)>) {
+    for (transform, mut interpolation) in query.iter_mut() {
+        let pose = (transform.translation, transform.rotation);
+        interpolation.previous = Some(interpolation.current.unwrap_or(pose));
+        interpolation.current = Some(pose);
+    }
+}
+fn interpolate_poses(
+    mut query: Query<(&mut Transform, &mut PoseInterpolation)>,
+    timesteps: Res<FixedTimesteps>,
+) {
+    let alpha = timesteps
+        .get(PHYSICS_TIMESTEP)
+        .map_or(1.0, |timestep| timestep.overstep_percentage() as f32);
+    for (mut transform, mut interpolation) in query.iter_mut() {
+        if let (Some(previous), Some(current)) =
+            (interpolation.previous, interpolation.current)
+        {
+            transform.translation = previous.0.lerp(current.0, alpha);
+            transform.rotation = previous.1.slerp(current.1, alpha);
+            interpolation.rendered = Some(*transform);
+        }
+    }
+}
@@ -0,0 +1,159 @@
+use super::{BiomeChanged, FuelTag, GameEntity, GameState, PlayerLabel};
+use crate::loading::FontAssets;
+use bevy::prelude::*;
+
+pub struct PickupPlugin;
+impl Plugin for PickupPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<FuelPickedUp>().add_system_set(
+            SystemSet::on_update(GameState::Game)
+                .with_system(magnet_fuel)
+                .with_system(upgrade_magnet)
+                .with_system(spawn_pickup_text)
+                .with_system(float_pickup_text),
+        );
+    }
+}
+/// Sent by `handle_collision` for every fuel pickup collected
+pub struct FuelPickedUp {
+    pub position: Vec2,
+    /// fuel that fit in the tanks
+    pub amount: f32,
+}
+/// Pulls nearby pickups toward the ship
+#[derive(Component)]
+pub struct FuelMagnet {
+    pub radius: f32,
+    /// speed pickups move at when right next to the ship
+    pub strength: f32,
+}
+impl FuelMagnet {
+    const START_RADIUS: f32 = 80.0;
+    const UPGRADE_RADIUS: f32 = 40.0;
+    const MAX_RADIUS: f32 = 240.0;
+    /// grows the radius by one step up to the maximum
+    pub fn upgrade(&mut self) {
+        self.radius =
+            (self.radius + Self::UPGRADE_RADIUS).min(Self::MAX_RADIUS);
+    }
+}
+impl Default for FuelMagnet {
+    fn default() -> Self {
+        Self {
+            radius: Self::START_RADIUS,
+            strength: 600.0,
+        }
+    }
+}
+/// "+100 FUEL" text rising from a collected pickup
+#[derive(Component)]
+struct PickupText {
+    timer: Timer,
+}
+impl PickupText {
+    const SHOW_TIME_SEC: f32 = 0.8;
+    const RISE_SPEED: f32 = 120.0;
+}
+mod colors {
+    use bevy::prelude::*;
+
+    pub const PICKUP_TEXT_SIZE: f32 = 30.0;
+    pub const PICKUP_TEXT_COLOR: Color = Color::rgb(0.2, 1.0, 0.2);
+}
+#[allow(clippy::type_complexity)]
+fn magnet_fuel(
+    mut p_set: ParamSet<(
+        Query<(&Transform, &FuelMagnet), With<PlayerLabel>>,
+        Query<&mut Transform, With<FuelTag>>,
+    )>,
+    time: Res<Time>,
+) {
+    let (player_position, radius, strength) = match p_set.p0().iter().next() {
+        Some((transform, magnet)) => (
+            transform.translation.truncate(),
+            magnet.radius,
+            magnet.strength,
+        ),
+        None => {
+            error!("player not found");
+            return;
+        }
+    };
+    for mut transform in p_set.p1().iter_mut() {
+        let offset = player_position - transform.translation.truncate();
+        let distance = offset.length();
+        if distance > radius || distance == 0.0 {
+            continue;
+        }
+        // stronger the closer the pickup is so it snaps in at the end
+        let speed = strength * (1.0 - distance / radius);
+        let step = (speed * time.delta_seconds()).min(distance);
+        transform.translation += (offset / distance * step).extend(0.0);
+    }
+}
+/// every new biome reached grows the magnet
+fn upgrade_magnet(
+    mut biome_events: EventReader<BiomeChanged>,
+    mut magnet_query: Query<&mut FuelMagnet, With<PlayerLabel>>,
+) {
+    for _ in biome_events.iter() {
+        for mut magnet in magnet_query.iter_mut() {
+            magnet.upgrade();
+        }
+    }
+}
+fn spawn_pickup_text(
+    mut commands: Commands,
+    mut pickup_events: EventReader<FuelPickedUp>,
+    fonts: Res<FontAssets>,
+) {
+    for pickup in pickup_events.iter() {
+        commands
+            .spawn_bundle(Text2dBundle {
+                text: Text::from_section(
+                    if pickup.amount > 0.0 {
+                        format!("+{} FUEL", pickup.amount.round())
+                    } else {
+                        "TANKS FULL".to_string()
+                    },
+                    TextStyle {
+                        font: fonts.silkscreen_bold.clone(),
+                        font_size: colors::PICKUP_TEXT_SIZE,
+                        color: colors::PICKUP_TEXT_COLOR,
+                    },
+                )
+                .with_alignment(TextAlignment::CENTER),
+                transform: Transform::from_translation(
+                    pickup.position.extend(1.0),
+                ),
+                ..default()
+            })
+            .insert(PickupText {
+                timer: Timer::from_seconds(PickupText::SHOW_TIME_SEC, false),
+            })
+            .insert(GameEntity);
+    }
+}
+fn float_pickup_text(
+    mut commands: Commands,
+    mut text_query: Query<
+        (Entity, &mut PickupText, &mut Transform, &mut Text),
+        (),
+    >,
+    time: Res<Time>,
+) {
+    for (entity, mut pickup_text, mut transform, mut text) in
+        text_query.iter_mut()
+    {
+        pickup_text.timer.tick(time.delta());
+        if pickup_text.timer.finished() {
+            commands.entity(entity).despawn_recursive();
+            continue;
+        }
+        transform.translation.y +=
+            PickupText::RISE_SPEED * time.delta_seconds();
+        for section in text.sections.iter_mut() {
+            section.style.color.set_a(pickup_text.timer.percent_left());
+        }
+    }
+}
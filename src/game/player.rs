@@ -1,6 +1,12 @@
-use super::{GameEntity, GameState};
-use crate::loading::TextureAssets;
-use bevy::{prelude::*, sprite::MaterialMesh2dBundle};
+use super::{
+    ActiveModifiers, AnimationState, Checkpoints, Combo, CoolingModel,
+    CurrentBiome, ExhaustEmitter, FixedPhysicsStage, FlightDynamics,
+    FuelEconomy, FuelMagnet, GameEntity, GameLabel, GameState, Hull,
+    LastHeatLevel, PlayerFuel, PlayerProfile, PoseInterpolation, Ship,
+    TrailEmitter, PHYSICS_DT,
+};
+use crate::loading::{SheetAssets, SkinAssets};
+use bevy::{prelude::*, sprite::Anchor};
 use bevy_rapier2d::prelude::*;
 use std::time::Duration;
 pub struct PlayerPlugin;
@@ -8,197 +14,273 @@ pub struct PlayerPlugin;
 impl Plugin for PlayerPlugin {
     fn build(&self, app: &mut App) {
         app.add_system_set(
-            SystemSet::on_enter(GameState::Game).with_system(spawn_player),
+            SystemSet::on_enter(GameState::Game)
+                .with_system(spawn_player.after(GameLabel::Checkpoint)),
         )
-        .insert_resource(MaxScore::default())
-        .insert_resource(MaxScore::default())
+        .insert_resource(FlightModel::Arcade)
         .add_system_set(
             SystemSet::on_update(GameState::Game)
-                .with_system(input_system)
-                .with_system(player_cooling)
-                .with_system(update_score)
-                .with_system(update_camera_height),
-        );
-    }
-}
-pub struct MaxScore {
-    pub score: PlayerScore,
-}
-impl Default for MaxScore {
-    fn default() -> Self {
-        Self {
-            score: PlayerScore::new(),
-        }
+                .with_system(player_cooling.label(GameLabel::Cooling)),
+        )
+        .add_system_to_stage(FixedPhysicsStage::Restore, clear_fuel_burned)
+        .stage(FixedPhysicsStage::Step, |schedule: &mut Schedule| {
+            schedule.add_system_set_to_stage(
+                FixedPhysicsStage::Flight,
+                SystemSet::on_update(GameState::Game)
+                    .with_system(input_system.label(GameLabel::Thrust)),
+            )
+        });
     }
 }
 #[derive(Component)]
-pub struct PlayerCamera;
-#[derive(Component)]
-pub struct PlayerScore {
-    max_score: f32,
+pub struct PlayerLabel;
+/// How keyboard input moves the ship
+#[derive(Clone, Copy, Eq, PartialEq, Debug)]
+pub enum FlightModel {
+    /// WASD push the ship directly along both axes
+    Arcade,
+    /// A and D rotate the ship and W fires the engine along its facing
+    Lander,
 }
-
-impl PlayerScore {
-    pub fn set_score(&mut self, new_score: f32) {
-        self.max_score = new_score.max(self.max_score);
-    }
-    pub fn get_score(&self) -> f32 {
-        self.max_score
+impl FlightModel {
+    /// angular velocity in radians per second while turning
+    const TURN_SPEED: f32 = 3.0;
+    /// slows spinning down once the turn keys are released
+    const ANGULAR_DAMPING: f32 = 4.0;
+    pub fn name(&self) -> &'static str {
+        match self {
+            FlightModel::Arcade => "ARCADE",
+            FlightModel::Lander => "LANDER",
+        }
     }
-    pub fn new() -> Self {
-        Self { max_score: 0.0 }
+    pub fn next(&self) -> Self {
+        match self {
+            FlightModel::Arcade => FlightModel::Lander,
+            FlightModel::Lander => FlightModel::Arcade,
+        }
     }
 }
 #[derive(Component)]
-pub struct PlayerLabel;
-#[derive(Component)]
 pub struct PlayerCoolingTimer {
     pub timer: Timer,
 }
 impl PlayerCoolingTimer {
     pub const COOLING_TIME: Duration = Duration::from_secs(10);
-    pub fn new() -> Self {
+    pub fn with_duration(duration: Duration) -> Self {
         Self {
-            timer: Timer::new(Self::COOLING_TIME, false),
+            timer: Timer::new(duration, false),
         }
     }
     /// gets cooling used (goes from 0.0 to 1.0
     pub fn get_frac_used(&self) -> f32 {
         1.0 - self.timer.percent_left()
     }
+    /// seconds of cooling left before the ship overheats
+    pub fn seconds_left(&self) -> f32 {
+        self.timer
+            .duration()
+            .saturating_sub(self.timer.elapsed())
+            .as_secs_f32()
+    }
     ///resets cooling
     pub fn refill_cooling(&mut self) {
         self.timer.reset()
     }
-}
-#[derive(Component)]
-pub struct PlayerFuel {
-    amount: f32,
-    max: f32,
-}
-impl PlayerFuel {
-    pub fn new(max: f32) -> Self {
-        Self { amount: max, max }
-    }
-    pub fn get_fuel(&self) -> f32 {
-        self.amount
+    /// uses up `seconds` of cooling
+    pub fn add_heat(&mut self, seconds: f32) {
+        self.timer.tick(Duration::from_secs_f32(seconds));
     }
-    /// gets percent of fuel left in range 0.0 to 1.0
-    pub fn get_fuel_ratio_left(&self) -> f32 {
-        (self.amount) / self.max
+    /// gives back up to `seconds` of used cooling
+    pub fn shed_heat(&mut self, seconds: f32) {
+        let elapsed = self
+            .timer
+            .elapsed()
+            .saturating_sub(Duration::from_secs_f32(seconds));
+        self.timer.set_elapsed(elapsed);
     }
-    pub fn set_fuel(&mut self, amount: f32) {
-        self.amount = amount.max(0.0).min(self.max);
+}
+/// Thrust applied by the player, written by the input system every physics
+/// step
+#[derive(Component, Default)]
+pub struct PlayerThrust {
+    /// direction of the thrust in the last step, zero when not thrusting
+    pub direction: Vec2,
+    /// fuel burned by the steps of the last frame
+    pub fuel_burned: f32,
+}
+impl PlayerThrust {
+    /// the engine fired in the last step, also true in frames where no
+    /// step ran
+    pub fn is_thrusting(&self) -> bool {
+        self.direction != Vec2::ZERO
     }
-
-    pub fn add_fuel(&mut self, amount: f32) {
-        self.amount = (self.amount + amount).max(self.max)
+}
+fn clear_fuel_burned(mut thrust_query: Query<&mut PlayerThrust>) {
+    for mut thrust in thrust_query.iter_mut() {
+        thrust.fuel_burned = 0.0;
     }
 }
 fn player_cooling(
     mut player_query: Query<&mut PlayerCoolingTimer, ()>,
     time: Res<Time>,
+    current_biome: Res<CurrentBiome>,
+    cooling_model: Res<CoolingModel>,
     mut game_state: ResMut<State<GameState>>,
 ) {
     for mut player in player_query.iter_mut() {
-        player.timer.tick(time.delta());
+        // the heat model moves the timer itself
+        if *cooling_model == CoolingModel::Timer {
+            player.timer.tick(
+                time.delta().mul_f32(current_biome.biome.cooling_drain()),
+            );
+        }
         if player.timer.finished() {
             game_state.set(GameState::GameOver).unwrap()
         }
     }
 }
+#[allow(clippy::type_complexity)]
 fn input_system(
     keyboard_input: Res<Input<KeyCode>>,
-    time: Res<Time>,
+    modifiers: Res<ActiveModifiers>,
+    flight_model: Res<FlightModel>,
+    economy: Res<FuelEconomy>,
     mut player_position: Query<
-        (&mut Velocity, &mut PlayerFuel),
+        (
+            &Transform,
+            &mut Velocity,
+            &mut ExternalForce,
+            &ReadMassProperties,
+            &mut PlayerFuel,
+            &mut PlayerThrust,
+            &FlightDynamics,
+        ),
         With<PlayerLabel>,
     >,
 ) {
-    let player_speed = 1000.0;
-    for (mut p, mut fuel) in player_position.iter_mut() {
+    // runs once for every physics step, so thrust and fuel are the same at
+    // any frame rate
+    let step_time = PHYSICS_DT;
+    for (transform, mut p, mut force, mass, mut fuel, mut thrust, dynamics) in
+        player_position.iter_mut()
+    {
+        let player_speed = dynamics.thrust * modifiers.thrust_scale();
         let mut req_change = Vec2::new(0.0, 0.0);
 
-        if keyboard_input.pressed(KeyCode::A) {
-            req_change.x -= time.delta_seconds() * player_speed;
-        }
-        if keyboard_input.pressed(KeyCode::D) {
-            req_change.x += time.delta_seconds() * player_speed;
-        }
-        if keyboard_input.pressed(KeyCode::W) {
-            req_change.y += time.delta_seconds() * player_speed;
-        }
-        if keyboard_input.pressed(KeyCode::S) {
-            req_change.y -= time.delta_seconds() * player_speed;
+        match *flight_model {
+            FlightModel::Arcade => {
+                if keyboard_input.pressed(KeyCode::A) {
+                    req_change.x -= step_time * player_speed;
+                }
+                if keyboard_input.pressed(KeyCode::D) {
+                    req_change.x += step_time * player_speed;
+                }
+                if keyboard_input.pressed(KeyCode::W) {
+                    req_change.y += step_time * player_speed;
+                }
+                if keyboard_input.pressed(KeyCode::S) {
+                    req_change.y -= step_time * player_speed;
+                }
+            }
+            FlightModel::Lander => {
+                let mut turn = 0.0;
+                if keyboard_input.pressed(KeyCode::A) {
+                    turn += 1.0;
+                }
+                if keyboard_input.pressed(KeyCode::D) {
+                    turn -= 1.0;
+                }
+                // turning is free, only the main engine burns fuel
+                if turn != 0.0 {
+                    p.angvel = turn * FlightModel::TURN_SPEED;
+                }
+                if keyboard_input.pressed(KeyCode::W) {
+                    let facing = (transform.rotation * Vec3::Y).truncate();
+                    req_change += facing * step_time * player_speed;
+                }
+            }
         }
 
-        let mag = req_change.length();
-        let required = mag.min(fuel.get_fuel());
-        let change = required * req_change.normalize();
+        req_change *= dynamics
+            .thrust_factor(p.linvel.dot(req_change.normalize_or_zero()));
+        let burned = fuel.burn(economy.burn(req_change.length()));
+        let change = economy.delta_v(burned) * req_change.normalize();
 
         if change.is_nan() {
-            return;
+            thrust.direction = Vec2::ZERO;
+            force.force = Vec2::ZERO;
+            continue;
         }
-        p.linvel.x += change.x;
-        p.linvel.y += change.y;
-        let fuel_amount = fuel.get_fuel();
-        fuel.set_fuel((fuel_amount - required).max(0.0));
-    }
-}
-fn update_camera_height(
-    mut queries: ParamSet<(
-        Query<&Transform, With<PlayerLabel>>,
-        Query<&mut Transform, With<PlayerCamera>>,
-    )>,
-) {
-    let player_transform_query = queries.p0();
-    let player_y = player_transform_query.iter().next().clone();
-    if player_y.is_none() {
-        error!("player not found");
-        return;
-    }
-    let player_y = player_y.unwrap().translation.y;
-    for mut camera_transform in queries.p1().iter_mut() {
-        let camera_y = camera_transform.translation.y.max(player_y);
-        camera_transform.translation.y = camera_y;
-    }
-}
-fn update_score(
-    mut player_query: Query<(&Transform, &mut PlayerScore), With<PlayerLabel>>,
-    mut score: ResMut<MaxScore>,
-) {
-    for (transform, mut current_score) in player_query.iter_mut() {
-        current_score.set_score(transform.translation.y);
-        score.score.set_score(transform.translation.y);
+        // an empty tank leaves the engine cold
+        thrust.direction = if burned > 0.0 {
+            req_change.normalize()
+        } else {
+            Vec2::ZERO
+        };
+        thrust.fuel_burned += burned;
+        force.force = change / step_time * mass.0.mass;
     }
 }
+#[allow(clippy::too_many_arguments)]
 fn spawn_player(
     mut commands: Commands,
-    mut meshes: ResMut<Assets<Mesh>>,
-    mut materials: ResMut<Assets<ColorMaterial>>,
-    textures: Res<TextureAssets>,
+    sheets: Res<SheetAssets>,
+    skins: Res<SkinAssets>,
+    flight_model: Res<FlightModel>,
+    ship: Res<Ship>,
+    profile: Res<PlayerProfile>,
+    economy: Res<FuelEconomy>,
+    checkpoints: Res<Checkpoints>,
 ) {
-    let mut transform = Transform::from_translation(Vec3::new(0.0, 0.0, 0.0));
+    let mut transform =
+        Transform::from_translation(checkpoints.spawn_position().extend(0.0));
     transform.scale = Vec3::new(4.0, 4.0, 4.0);
+    let stats = ship.stats();
+    let dynamics = stats.dynamics;
     commands
-        .spawn_bundle(Camera2dBundle::default())
-        .insert(GameEntity)
-        .insert(PlayerCamera);
-    let radius = 10.0;
-    commands
-        .spawn_bundle(SpriteBundle {
-            texture: textures.spaceship.clone(),
+        .spawn_bundle(SpriteSheetBundle {
+            texture_atlas: profile.get_skin().sheet(*ship, &sheets, &skins),
+            sprite: TextureAtlasSprite {
+                // frames leave room below the ship for the engine flame
+                anchor: Anchor::Custom(Vec2::new(0.0, 0.05)),
+                ..default()
+            },
             transform,
             ..default()
         })
+        .insert(AnimationState::new("ship_idle"))
         .insert(PlayerLabel)
-        .insert(Collider::ball(radius))
+        .insert(Collider::ball(stats.collider_radius))
         .insert(ActiveEvents::all())
         .insert(RigidBody::Dynamic)
         .insert(Velocity::zero())
-        .insert(Restitution::new(1.0))
-        .insert(PlayerFuel::new(1000.0))
-        .insert(PlayerCoolingTimer::new())
+        .insert(ExternalForce::default())
+        .insert(ExternalImpulse::default())
+        .insert(ReadMassProperties::default())
+        .insert(PoseInterpolation::default())
+        .insert(GravityScale(dynamics.gravity_scale))
+        .insert(Damping {
+            angular_damping: FlightModel::ANGULAR_DAMPING,
+            ..default()
+        })
+        .insert(match *flight_model {
+            FlightModel::Arcade => LockedAxes::ROTATION_LOCKED,
+            FlightModel::Lander => LockedAxes::empty(),
+        })
+        .insert(Restitution::new(dynamics.restitution))
+        .insert(dynamics)
+        .insert(match checkpoints.resumed() {
+            Some(snapshot) => snapshot.fuel.clone(),
+            None => economy.tanks(stats.fuel_capacity),
+        })
+        .insert(PlayerThrust::default())
+        .insert(ExhaustEmitter::default())
+        .insert(TrailEmitter::default())
+        .insert(FuelMagnet::default())
+        .insert(PlayerCoolingTimer::with_duration(stats.cooling_time))
+        .insert(LastHeatLevel::default())
+        .insert(Hull::new(stats.hull))
+        .insert(Combo::default())
         .insert(GameEntity)
-        .insert(PlayerScore::new());
+        .insert(checkpoints.starting_score());
 }
@@ -0,0 +1,215 @@
+use super::{Biome, Checkpoints, GameState, PlayerLabel, PlayerScore, Ship};
+use crate::{
+    loading::{SheetAssets, SkinAssets},
+    storage,
+};
+use bevy::prelude::*;
+
+pub struct ProfilePlugin;
+impl Plugin for ProfilePlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(PlayerProfile::load())
+            .add_system_set(
+                SystemSet::on_enter(GameState::Game)
+                    .with_system(clear_unlocked),
+            )
+            .add_system_set(
+                SystemSet::on_exit(GameState::Game).with_system(record_run),
+            );
+    }
+}
+/// Cosmetic palette swaps of the ships
+#[derive(Clone, Copy, Eq, PartialEq, Debug)]
+pub enum Skin {
+    Default,
+    Ember,
+    Arctic,
+    Gold,
+}
+/// What has to be done to unlock a skin
+pub enum Unlock {
+    Always,
+    /// altitude summed over every run
+    TotalAltitude(f32),
+    /// altitude reached in a single run
+    ReachAltitude(f32),
+}
+impl Skin {
+    pub const ALL: [Skin; 4] =
+        [Skin::Default, Skin::Ember, Skin::Arctic, Skin::Gold];
+    pub fn name(&self) -> &'static str {
+        match self {
+            Skin::Default => "DEFAULT",
+            Skin::Ember => "EMBER",
+            Skin::Arctic => "ARCTIC",
+            Skin::Gold => "GOLD",
+        }
+    }
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.iter().find(|skin| skin.name() == name).copied()
+    }
+    pub fn unlock(&self) -> Unlock {
+        match self {
+            Skin::Default => Unlock::Always,
+            Skin::Ember => Unlock::TotalAltitude(50000.0),
+            Skin::Arctic => {
+                Unlock::ReachAltitude(Biome::Orbit.start_altitude())
+            }
+            Skin::Gold => Unlock::TotalAltitude(250000.0),
+        }
+    }
+    /// sprite sheet of `ship` painted in this skin
+    pub fn sheet(
+        &self,
+        ship: Ship,
+        sheets: &SheetAssets,
+        skins: &SkinAssets,
+    ) -> Handle<TextureAtlas> {
+        match (self, ship) {
+            (Skin::Default, _) => ship.sheet(sheets),
+            (Skin::Ember, Ship::Standard) => skins.ember_standard.clone(),
+            (Skin::Ember, Ship::Tanker) => skins.ember_tanker.clone(),
+            (Skin::Ember, Ship::Racer) => skins.ember_racer.clone(),
+            (Skin::Arctic, Ship::Standard) => skins.arctic_standard.clone(),
+            (Skin::Arctic, Ship::Tanker) => skins.arctic_tanker.clone(),
+            (Skin::Arctic, Ship::Racer) => skins.arctic_racer.clone(),
+            (Skin::Gold, Ship::Standard) => skins.gold_standard.clone(),
+            (Skin::Gold, Ship::Tanker) => skins.gold_tanker.clone(),
+            (Skin::Gold, Ship::Racer) => skins.gold_racer.clone(),
+        }
+    }
+}
+/// Progress kept between sessions
+pub struct PlayerProfile {
+    /// altitude summed over every run
+    pub total_altitude: f32,
+    /// highest altitude reached in a single run
+    pub best_altitude: f32,
+    pub runs: u32,
+    /// set once the tutorial has been started, after that it is only played
+    /// from the menu
+    seen_tutorial: bool,
+    skin: Skin,
+    /// skins unlocked by the last run
+    newly_unlocked: Vec<Skin>,
+}
+impl PlayerProfile {
+    const SAVE_KEY: &'static str = "profile";
+    pub fn load() -> Self {
+        let mut profile = Self {
+            total_altitude: 0.0,
+            best_altitude: 0.0,
+            runs: 0,
+            seen_tutorial: false,
+            skin: Skin::Default,
+            newly_unlocked: Vec::new(),
+        };
+        if let Some(data) = storage::load(Self::SAVE_KEY) {
+            let fields = storage::parse_fields(&data);
+            if let Some(total) =
+                fields.get("total").and_then(|t| t.parse().ok())
+            {
+                profile.total_altitude = total;
+            }
+            if let Some(best) = fields.get("best").and_then(|b| b.parse().ok())
+            {
+                profile.best_altitude = best;
+            }
+            if let Some(runs) = fields.get("runs").and_then(|r| r.parse().ok())
+            {
+                profile.runs = runs;
+            }
+            if let Some(seen) =
+                fields.get("tutorial").and_then(|t| t.parse().ok())
+            {
+                profile.seen_tutorial = seen;
+            }
+            if let Some(skin) =
+                fields.get("skin").and_then(|s| Skin::from_name(s))
+            {
+                if profile.is_unlocked(skin) {
+                    profile.skin = skin;
+                }
+            }
+        }
+        profile
+    }
+    pub fn save(&self) {
+        storage::save(
+            Self::SAVE_KEY,
+            &storage::write_fields(&[
+                ("total", self.total_altitude.to_string()),
+                ("best", self.best_altitude.to_string()),
+                ("runs", self.runs.to_string()),
+                ("tutorial", self.seen_tutorial.to_string()),
+                ("skin", self.skin.name().to_string()),
+            ]),
+        );
+    }
+    pub fn is_unlocked(&self, skin: Skin) -> bool {
+        match skin.unlock() {
+            Unlock::Always => true,
+            Unlock::TotalAltitude(altitude) => self.total_altitude >= altitude,
+            Unlock::ReachAltitude(altitude) => self.best_altitude >= altitude,
+        }
+    }
+    pub fn get_skin(&self) -> Skin {
+        self.skin
+    }
+    /// selects the next unlocked skin and saves the choice
+    pub fn next_skin(&mut self) {
+        let start = Skin::ALL.iter().position(|s| *s == self.skin).unwrap_or(0);
+        for offset in 1..=Skin::ALL.len() {
+            let skin = Skin::ALL[(start + offset) % Skin::ALL.len()];
+            if self.is_unlocked(skin) {
+                self.skin = skin;
+                break;
+            }
+        }
+        self.save();
+    }
+    pub fn has_seen_tutorial(&self) -> bool {
+        self.seen_tutorial
+    }
+    /// stops the tutorial from starting on its own and saves
+    pub fn mark_tutorial_seen(&mut self) {
+        self.seen_tutorial = true;
+        self.save();
+    }
+    pub fn get_newly_unlocked(&self) -> &[Skin] {
+        &self.newly_unlocked
+    }
+    /// adds a finished run to the totals, a continued run only adds the
+    /// `gained` altitude the runs before it did not reach
+    fn record_run(&mut self, altitude: f32, gained: f32, continued: bool) {
+        let locked: Vec<Skin> = Skin::ALL
+            .iter()
+            .copied()
+            .filter(|skin| !self.is_unlocked(*skin))
+            .collect();
+        if !continued {
+            self.runs += 1;
+        }
+        self.total_altitude += gained;
+        self.best_altitude = self.best_altitude.max(altitude);
+        self.newly_unlocked = locked
+            .into_iter()
+            .filter(|skin| self.is_unlocked(*skin))
+            .collect();
+    }
+}
+fn clear_unlocked(mut profile: ResMut<PlayerProfile>) {
+    profile.newly_unlocked.clear();
+}
+fn record_run(
+    player_query: Query<&PlayerScore, With<PlayerLabel>>,
+    mut profile: ResMut<PlayerProfile>,
+    mut checkpoints: ResMut<Checkpoints>,
+) {
+    for score in player_query.iter() {
+        let altitude = score.get_altitude();
+        let gained = checkpoints.record_altitude(altitude);
+        profile.record_run(altitude, gained, checkpoints.resumed().is_some());
+    }
+    profile.save();
+}
@@ -0,0 +1,342 @@
+use super::{
+    camera::CameraLabel, meters, CheckpointPlatform, Drone, FuelTag,
+    GameEntity, GameState, Ghost, Hazard, PlayerCamera, PlayerLabel,
+    FUEL_MAX_Y_DIST, WORLD_HALF_WIDTH,
+};
+use crate::loading::FontAssets;
+use bevy::{prelude::*, sprite::MaterialMesh2dBundle};
+
+/// Points the player toward things that are not on screen, with arrows at
+/// the edge of the screen and an optional minimap
+pub struct RadarPlugin;
+impl Plugin for RadarPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(MinimapMode::Shown)
+            .init_resource::<IndicatorAssets>()
+            .add_system_set(
+                SystemSet::on_enter(GameState::Game)
+                    .with_system(spawn_indicators)
+                    .with_system(spawn_minimap),
+            )
+            .add_system_set(
+                SystemSet::on_update(GameState::Game)
+                    .with_system(point_indicators.after(CameraLabel::Shake))
+                    .with_system(draw_minimap),
+            );
+    }
+}
+/// Whether the minimap is drawn during a run
+#[derive(Clone, Copy, Eq, PartialEq, Debug)]
+pub enum MinimapMode {
+    Hidden,
+    Shown,
+}
+impl MinimapMode {
+    pub fn name(&self) -> &'static str {
+        match self {
+            MinimapMode::Hidden => "OFF",
+            MinimapMode::Shown => "ON",
+        }
+    }
+    pub fn next(&self) -> Self {
+        match self {
+            MinimapMode::Hidden => MinimapMode::Shown,
+            MinimapMode::Shown => MinimapMode::Hidden,
+        }
+    }
+}
+/// Arrow at the edge of the screen pointing at an off screen pickup, one
+/// per slot with the nearest pickup in slot 0
+#[derive(Component)]
+struct PickupIndicator {
+    slot: usize,
+}
+/// distance to the pickup of the [`PickupIndicator`] in the same slot
+#[derive(Component)]
+struct IndicatorLabel {
+    slot: usize,
+}
+impl PickupIndicator {
+    const MAX_INDICATORS: usize = 3;
+    const SIZE: f32 = 16.0;
+    /// distance from the edge of the screen in screen pixels
+    const MARGIN: f32 = 40.0;
+    /// the label sits this much further inside than the arrow
+    const LABEL_OFFSET: f32 = 36.0;
+}
+/// Narrow strip showing everything within [`FUEL_MAX_Y_DIST`] of the ship
+#[derive(Component)]
+struct Minimap;
+impl Minimap {
+    /// dots spawned with the minimap, anything past this is not drawn
+    const MAX_DOTS: usize = 64;
+}
+/// One of the dots of the [`Minimap`], reused every frame
+#[derive(Component)]
+struct MinimapDot {
+    slot: usize,
+}
+mod colors {
+    use bevy::prelude::*;
+
+    pub const INDICATOR_COLOR: Color = Color::rgb(0.2, 1.0, 0.2);
+    pub const LABEL_SIZE: f32 = 20.0;
+    pub const MINIMAP_COLOR: Color = Color::rgba(0.0, 0.0, 0.0, 0.4);
+    pub const MINIMAP_DOT_SIZE: f32 = 6.0;
+    pub const SHIP_DOT_COLOR: Color = Color::rgb(1.0, 1.0, 1.0);
+    pub const GHOST_DOT_COLOR: Color = Color::rgba(1.0, 1.0, 1.0, 0.4);
+    pub const PICKUP_DOT_COLOR: Color = Color::rgb(0.2, 1.0, 0.2);
+    pub const HAZARD_DOT_COLOR: Color = Color::rgb(1.0, 0.2, 0.2);
+    pub const CHECKPOINT_DOT_COLOR: Color = Color::rgb(0.4, 0.6, 1.0);
+}
+/// Mesh and material of the indicator arrows, built once
+struct IndicatorAssets {
+    mesh: Handle<Mesh>,
+    material: Handle<ColorMaterial>,
+}
+impl FromWorld for IndicatorAssets {
+    fn from_world(world: &mut World) -> Self {
+        let mesh = world
+            .resource_mut::<Assets<Mesh>>()
+            .add(shape::RegularPolygon::new(PickupIndicator::SIZE, 3).into());
+        let material = world
+            .resource_mut::<Assets<ColorMaterial>>()
+            .add(ColorMaterial::from(colors::INDICATOR_COLOR));
+        Self { mesh, material }
+    }
+}
+fn spawn_indicators(
+    mut commands: Commands,
+    fonts: Res<FontAssets>,
+    assets: Res<IndicatorAssets>,
+) {
+    for slot in 0..PickupIndicator::MAX_INDICATORS {
+        commands
+            .spawn_bundle(MaterialMesh2dBundle {
+                mesh: assets.mesh.clone().into(),
+                material: assets.material.clone(),
+                visibility: Visibility { is_visible: false },
+                ..default()
+            })
+            .insert(PickupIndicator { slot })
+            .insert(GameEntity);
+        commands
+            .spawn_bundle(Text2dBundle {
+                text: Text::from_section(
+                    "",
+                    TextStyle {
+                        font: fonts.silkscreen_bold.clone(),
+                        font_size: colors::LABEL_SIZE,
+                        color: colors::INDICATOR_COLOR,
+                    },
+                )
+                .with_alignment(TextAlignment::CENTER),
+                visibility: Visibility { is_visible: false },
+                ..default()
+            })
+            .insert(IndicatorLabel { slot })
+            .insert(GameEntity);
+    }
+}
+#[allow(clippy::type_complexity)]
+fn point_indicators(
+    camera_query: Query<
+        (&Transform, &OrthographicProjection),
+        With<PlayerCamera>,
+    >,
+    player_query: Query<&Transform, With<PlayerLabel>>,
+    fuel_query: Query<&Transform, With<FuelTag>>,
+    mut indicator_query: Query<
+        (&PickupIndicator, &mut Transform, &mut Visibility),
+        (
+            Without<PlayerCamera>,
+            Without<PlayerLabel>,
+            Without<FuelTag>,
+        ),
+    >,
+    mut label_query: Query<
+        (&IndicatorLabel, &mut Transform, &mut Visibility, &mut Text),
+        (
+            Without<PlayerCamera>,
+            Without<PlayerLabel>,
+            Without<FuelTag>,
+            Without<PickupIndicator>,
+        ),
+    >,
+) {
+    let (camera, projection) = match camera_query.iter().next() {
+        Some(camera) => camera,
+        None => {
+            error!("camera not found");
+            return;
+        }
+    };
+    let player = match player_query.iter().next() {
+        Some(transform) => transform.translation.truncate(),
+        None => {
+            error!("player not found");
+            return;
+        }
+    };
+    let center = camera.translation.truncate();
+    // sizes on screen stay the same while the camera zooms
+    let scale = projection.scale;
+    let half_view = Vec2::new(projection.right, projection.top) * scale;
+    let edge = half_view - Vec2::splat(PickupIndicator::MARGIN * scale);
+    let mut off_screen: Vec<Vec2> = fuel_query
+        .iter()
+        .map(|transform| transform.translation.truncate())
+        .filter(|position| {
+            let offset = (*position - center).abs();
+            offset.x > half_view.x || offset.y > half_view.y
+        })
+        .collect();
+    off_screen.sort_by(|a, b| {
+        a.distance_squared(player)
+            .total_cmp(&b.distance_squared(player))
+    });
+    // where the line from the screen center to the pickup leaves the
+    // shrunk screen rectangle
+    let placement = |position: Vec2, inset: Vec2| {
+        let offset = position - center;
+        let fit = (inset / offset.abs()).min_element();
+        (center + offset * fit, offset)
+    };
+    for (indicator, mut transform, mut visibility) in indicator_query.iter_mut()
+    {
+        let target = off_screen.get(indicator.slot);
+        visibility.is_visible = target.is_some();
+        if let Some(target) = target {
+            let (position, offset) = placement(*target, edge);
+            transform.translation = position.extend(1.0);
+            transform.rotation = Quat::from_rotation_z(
+                offset.y.atan2(offset.x) - std::f32::consts::FRAC_PI_2,
+            );
+            transform.scale = Vec3::splat(scale);
+        }
+    }
+    let label_edge = edge - Vec2::splat(PickupIndicator::LABEL_OFFSET * scale);
+    for (label, mut transform, mut visibility, mut text) in
+        label_query.iter_mut()
+    {
+        let target = off_screen.get(label.slot);
+        visibility.is_visible = target.is_some();
+        if let Some(target) = target {
+            let (position, _) = placement(*target, label_edge);
+            transform.translation = position.extend(1.0);
+            transform.scale = Vec3::splat(scale);
+            text.sections[0].value =
+                format!("{} m", meters(target.distance(player)));
+        }
+    }
+}
+fn spawn_minimap(mut commands: Commands, mode: Res<MinimapMode>) {
+    if *mode == MinimapMode::Hidden {
+        return;
+    }
+    commands
+        .spawn_bundle(NodeBundle {
+            style: Style {
+                position_type: PositionType::Absolute,
+                position: UiRect {
+                    right: Val::Percent(6.0),
+                    top: Val::Percent(20.0),
+                    ..default()
+                },
+                size: Size::new(Val::Percent(4.0), Val::Percent(60.0)),
+                ..default()
+            },
+            color: colors::MINIMAP_COLOR.into(),
+            ..default()
+        })
+        .insert(Minimap)
+        .insert(GameEntity)
+        .with_children(|parent| {
+            for slot in 0..Minimap::MAX_DOTS {
+                parent
+                    .spawn_bundle(NodeBundle {
+                        style: Style {
+                            position_type: PositionType::Absolute,
+                            size: Size::new(
+                                Val::Px(colors::MINIMAP_DOT_SIZE),
+                                Val::Px(colors::MINIMAP_DOT_SIZE),
+                            ),
+                            ..default()
+                        },
+                        visibility: Visibility { is_visible: false },
+                        ..default()
+                    })
+                    .insert(MinimapDot { slot });
+            }
+        });
+}
+#[allow(clippy::type_complexity)]
+fn draw_minimap(
+    mut dot_query: Query<(
+        &MinimapDot,
+        &mut Style,
+        &mut UiColor,
+        &mut Visibility,
+    )>,
+    player_query: Query<&Transform, With<PlayerLabel>>,
+    fuel_query: Query<&Transform, With<FuelTag>>,
+    hazard_query: Query<&GlobalTransform, Or<(With<Hazard>, With<Drone>)>>,
+    checkpoint_query: Query<&GlobalTransform, With<CheckpointPlatform>>,
+    ghost: Res<Ghost>,
+) {
+    if dot_query.is_empty() {
+        return;
+    }
+    let player = match player_query.iter().next() {
+        Some(transform) => transform.translation.truncate(),
+        None => {
+            error!("player not found");
+            return;
+        }
+    };
+    // the ship and its ghost go first so they are never left out
+    let dots: Vec<(Vec2, Color)> =
+        std::iter::once((player, colors::SHIP_DOT_COLOR))
+            .chain(
+                ghost
+                    .position()
+                    .map(|position| (position, colors::GHOST_DOT_COLOR)),
+            )
+            .chain(fuel_query.iter().map(|transform| {
+                (transform.translation.truncate(), colors::PICKUP_DOT_COLOR)
+            }))
+            .chain(hazard_query.iter().map(|transform| {
+                (transform.translation().truncate(), colors::HAZARD_DOT_COLOR)
+            }))
+            .chain(checkpoint_query.iter().map(|transform| {
+                (
+                    transform.translation().truncate(),
+                    colors::CHECKPOINT_DOT_COLOR,
+                )
+            }))
+            .filter_map(|(position, color)| {
+                let x =
+                    (position.x + WORLD_HALF_WIDTH) / (2.0 * WORLD_HALF_WIDTH);
+                let y = (position.y - player.y + FUEL_MAX_Y_DIST)
+                    / (2.0 * FUEL_MAX_Y_DIST);
+                ((0.0..=1.0).contains(&x) && (0.0..=1.0).contains(&y))
+                    .then_some((Vec2::new(x, y), color))
+            })
+            .collect();
+    for (dot, mut style, mut ui_color, mut visibility) in dot_query.iter_mut() {
+        let (position, color) = match dots.get(dot.slot) {
+            Some(dot) => *dot,
+            None => {
+                visibility.is_visible = false;
+                continue;
+            }
+        };
+        visibility.is_visible = true;
+        style.position = UiRect {
+            left: Val::Percent(100.0 * position.x),
+            bottom: Val::Percent(100.0 * position.y),
+            ..default()
+        };
+        ui_color.0 = color;
+    }
+}
@@ -0,0 +1,309 @@
+use super::{
+    FuelPickedUp, GameState, Hazard, PlayerLabel, Ship, PIXELS_PER_METER,
+};
+use bevy::{
+    prelude::*,
+    utils::{HashMap, HashSet},
+};
+use bevy_rapier2d::prelude::*;
+use std::time::Duration;
+
+pub struct ScorePlugin;
+impl Plugin for ScorePlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(MaxScore::default())
+            .insert_resource(NearHazards::default())
+            .add_event::<NearMiss>()
+            .add_system_set(
+                SystemSet::on_enter(GameState::Game)
+                    .with_system(clear_near_hazards),
+            )
+            .add_system_set(
+                SystemSet::on_update(GameState::Game)
+                    .with_system(update_score)
+                    .with_system(score_pickups)
+                    .with_system(detect_near_misses),
+            );
+    }
+}
+/// whole meters above the starting height at `pixels` altitude, the ship
+/// starts a run at zero
+pub fn meters(pixels: f32) -> u32 {
+    (pixels / PIXELS_PER_METER).max(0.0) as u32
+}
+pub struct MaxScore {
+    pub score: PlayerScore,
+    /// ship the score was set with
+    pub ship: Ship,
+}
+impl Default for MaxScore {
+    fn default() -> Self {
+        Self {
+            score: PlayerScore::new(),
+            ship: Ship::Standard,
+        }
+    }
+}
+/// Score of a run. Every meter climbed is worth a point, pickup combos and
+/// near misses add bonus points on top.
+#[derive(Component, Clone)]
+pub struct PlayerScore {
+    /// highest altitude reached in pixels
+    max_altitude: f32,
+    /// points from pickups and near misses
+    bonus: u32,
+    /// taken off the score for continuing from checkpoints
+    penalty: u32,
+}
+impl PlayerScore {
+    pub fn new() -> Self {
+        Self {
+            max_altitude: 0.0,
+            bonus: 0,
+            penalty: 0,
+        }
+    }
+    /// a score known only by its points, such as one loaded from a save
+    pub fn from_points(points: u32) -> Self {
+        Self {
+            bonus: points,
+            ..Self::new()
+        }
+    }
+    pub fn set_altitude(&mut self, altitude: f32) {
+        self.max_altitude = altitude.max(self.max_altitude);
+    }
+    /// highest altitude reached in pixels
+    pub fn get_altitude(&self) -> f32 {
+        self.max_altitude
+    }
+    pub fn add_bonus(&mut self, points: u32) {
+        self.bonus += points;
+    }
+    pub fn penalize(&mut self, points: u32) {
+        self.penalty += points;
+    }
+    pub fn get_score(&self) -> u32 {
+        (meters(self.max_altitude) + self.bonus).saturating_sub(self.penalty)
+    }
+}
+/// Pickup chain of the ship. Collecting pickups in quick succession grows
+/// the multiplier, once the chain breaks it drops back a step at a time.
+#[derive(Component)]
+pub struct Combo {
+    multiplier: u32,
+    /// time left to keep the chain going, or until the next decay step
+    timer: Timer,
+}
+impl Combo {
+    const MAX_MULTIPLIER: u32 = 8;
+    /// a pickup this soon after the last one grows the multiplier
+    const CHAIN_TIME_SEC: f32 = 2.0;
+    /// time between decay steps once the chain is broken
+    const DECAY_TIME_SEC: f32 = 1.0;
+    pub fn get_multiplier(&self) -> u32 {
+        self.multiplier
+    }
+    /// counts a pickup toward the chain
+    pub fn chain(&mut self) {
+        if !self.timer.finished() {
+            self.multiplier = (self.multiplier + 1).min(Self::MAX_MULTIPLIER);
+        }
+        self.timer
+            .set_duration(Duration::from_secs_f32(Self::CHAIN_TIME_SEC));
+        self.timer.reset();
+    }
+    pub fn tick(&mut self, delta: Duration) {
+        if !self.timer.tick(delta).just_finished() || self.multiplier <= 1 {
+            return;
+        }
+        self.multiplier -= 1;
+        self.timer
+            .set_duration(Duration::from_secs_f32(Self::DECAY_TIME_SEC));
+        self.timer.reset();
+    }
+}
+impl Default for Combo {
+    fn default() -> Self {
+        let mut timer = Timer::from_seconds(Self::CHAIN_TIME_SEC, false);
+        timer.tick(timer.duration());
+        Self {
+            multiplier: 1,
+            timer,
+        }
+    }
+}
+/// points for a pickup before the combo multiplier
+const PICKUP_POINTS: u32 = 5;
+/// points for a near miss before the combo multiplier
+const NEAR_MISS_POINTS: u32 = 10;
+/// a hazard passing this close to the hull without touching it is a near
+/// miss
+const NEAR_MISS_DISTANCE: f32 = 40.0;
+/// Sent when a hazard passes close by the ship without touching it
+pub struct NearMiss {
+    /// bonus with the combo multiplier applied
+    pub points: u32,
+}
+/// Hazards near the ship during a run
+#[derive(Default)]
+struct NearHazards {
+    /// hazards within [`NEAR_MISS_DISTANCE`] of the ship and whether they
+    /// touched it while close
+    close: HashMap<Entity, bool>,
+    /// hazards already paid out, a hazard is only worth one near miss
+    paid: HashSet<Entity>,
+}
+fn clear_near_hazards(mut near: ResMut<NearHazards>) {
+    near.close.clear();
+    near.paid.clear();
+}
+fn update_score(
+    mut player_query: Query<(&Transform, &mut PlayerScore), With<PlayerLabel>>,
+    mut max_score: ResMut<MaxScore>,
+    ship: Res<Ship>,
+) {
+    for (transform, mut score) in player_query.iter_mut() {
+        score.set_altitude(transform.translation.y);
+        if score.get_score() > max_score.score.get_score() {
+            max_score.score = score.clone();
+            max_score.ship = *ship;
+        }
+    }
+}
+fn score_pickups(
+    mut player_query: Query<(&mut PlayerScore, &mut Combo), With<PlayerLabel>>,
+    mut pickup_events: EventReader<FuelPickedUp>,
+    time: Res<Time>,
+) {
+    for (mut score, mut combo) in player_query.iter_mut() {
+        combo.tick(time.delta());
+        for _ in pickup_events.iter() {
+            combo.chain();
+            score.add_bonus(PICKUP_POINTS * combo.get_multiplier());
+        }
+    }
+}
+fn detect_near_misses(
+    mut player_query: Query<
+        (Entity, &Transform, &Collider, &mut PlayerScore, &Combo),
+        With<PlayerLabel>,
+    >,
+    hazard_query: Query<(), With<Hazard>>,
+    mut collision_events: EventReader<CollisionEvent>,
+    mut near_miss_events: EventWriter<NearMiss>,
+    mut near: ResMut<NearHazards>,
+    rapier_context: Res<RapierContext>,
+) {
+    let (player, transform, collider, mut score, combo) =
+        match player_query.iter_mut().next() {
+            Some(player) => player,
+            None => {
+                error!("player not found");
+                return;
+            }
+        };
+    for event in collision_events.iter() {
+        if let CollisionEvent::Started(e1, e2, _) = event {
+            let other = if *e1 == player { *e2 } else { *e1 };
+            if (*e1 == player || *e2 == player) && hazard_query.contains(other)
+            {
+                near.close.insert(other, true);
+                // a hazard that hit the ship is never a near miss
+                near.paid.insert(other);
+            }
+        }
+    }
+    let radius = collider.as_ball().map_or(10.0, |ball| ball.radius())
+        * transform.scale.x
+        + NEAR_MISS_DISTANCE;
+    let mut close = Vec::new();
+    rapier_context.intersections_with_shape(
+        transform.translation.truncate(),
+        0.0,
+        &Collider::ball(radius),
+        QueryFilter::new()
+            .exclude_collider(player)
+            .exclude_sensors(),
+        |entity| {
+            if hazard_query.contains(entity) {
+                close.push(entity);
+            }
+            true
+        },
+    );
+    for entity in close.iter() {
+        near.close.entry(*entity).or_insert(false);
+    }
+    let passed: Vec<(Entity, bool)> = near
+        .close
+        .iter()
+        .filter(|(entity, _)| !close.contains(*entity))
+        .map(|(entity, touched)| (*entity, *touched))
+        .collect();
+    for (entity, touched) in passed {
+        near.close.remove(&entity);
+        // despawned along with its chunk rather than flown past, or already
+        // paid for by hovering in and out of range
+        if touched
+            || !hazard_query.contains(entity)
+            || !near.paid.insert(entity)
+        {
+            continue;
+        }
+        let points = NEAR_MISS_POINTS * combo.get_multiplier();
+        score.add_bonus(points);
+        near_miss_events.send(NearMiss { points });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn score_is_whole_meters_climbed() {
+        let mut score = PlayerScore::new();
+        score.set_altitude(1234.5677);
+        assert_eq!(score.get_score(), 12);
+        score.set_altitude(-500.0);
+        assert_eq!(score.get_score(), 12);
+    }
+    #[test]
+    fn penalty_never_goes_below_zero() {
+        let mut score = PlayerScore::new();
+        score.set_altitude(1000.0);
+        score.add_bonus(5);
+        score.penalize(10);
+        assert_eq!(score.get_score(), 5);
+        score.penalize(100);
+        assert_eq!(score.get_score(), 0);
+    }
+    #[test]
+    fn quick_pickups_grow_the_multiplier() {
+        let mut combo = Combo::default();
+        combo.chain();
+        assert_eq!(combo.get_multiplier(), 1);
+        combo.tick(Duration::from_secs_f32(1.0));
+        combo.chain();
+        combo.chain();
+        assert_eq!(combo.get_multiplier(), 3);
+        for _ in 0..10 {
+            combo.chain();
+        }
+        assert_eq!(combo.get_multiplier(), Combo::MAX_MULTIPLIER);
+    }
+    #[test]
+    fn broken_chain_decays_one_step_at_a_time() {
+        let mut combo = Combo::default();
+        combo.chain();
+        combo.chain();
+        combo.chain();
+        combo.tick(Duration::from_secs_f32(Combo::CHAIN_TIME_SEC));
+        assert_eq!(combo.get_multiplier(), 2);
+        combo.tick(Duration::from_secs_f32(Combo::DECAY_TIME_SEC));
+        assert_eq!(combo.get_multiplier(), 1);
+        combo.tick(Duration::from_secs_f32(10.0));
+        assert_eq!(combo.get_multiplier(), 1);
+    }
+}
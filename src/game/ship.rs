@@ -0,0 +1,222 @@
+use super::{
+    ui::{button_clicked, colors, spawn_sized_button},
+    FlightDynamics, GameState, PlayerCoolingTimer,
+};
+use crate::loading::{FontAssets, SheetAssets, TextureAssets};
+use bevy::prelude::*;
+use std::time::Duration;
+
+pub struct ShipPlugin;
+impl Plugin for ShipPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(Ship::Standard)
+            .add_system_set(
+                SystemSet::on_enter(GameState::ShipSelect)
+                    .with_system(spawn_ship_select),
+            )
+            .add_system_set(
+                SystemSet::on_update(GameState::ShipSelect)
+                    .with_system(ship_buttons)
+                    .with_system(back_button),
+            )
+            .add_system_set(
+                SystemSet::on_exit(GameState::ShipSelect)
+                    .with_system(clear_ship_select),
+            );
+    }
+}
+/// Ships the player can fly, the selected one is a resource read when the
+/// player spawns
+#[derive(Clone, Copy, Eq, PartialEq, Debug)]
+pub enum Ship {
+    Standard,
+    /// lots of fuel and slow to heat up but sluggish
+    Tanker,
+    /// fast but heats up quickly and carries little fuel
+    Racer,
+}
+/// Everything that differs between ships
+pub struct ShipStats {
+    pub fuel_capacity: f32,
+    pub cooling_time: Duration,
+    pub collider_radius: f32,
+    pub hull: f32,
+    pub dynamics: FlightDynamics,
+}
+impl Ship {
+    pub const ALL: [Ship; 3] = [Ship::Standard, Ship::Tanker, Ship::Racer];
+    pub fn name(&self) -> &'static str {
+        match self {
+            Ship::Standard => "STANDARD",
+            Ship::Tanker => "TANKER",
+            Ship::Racer => "RACER",
+        }
+    }
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.iter().find(|ship| ship.name() == name).copied()
+    }
+    pub fn stats(&self) -> ShipStats {
+        match self {
+            Ship::Standard => ShipStats {
+                fuel_capacity: 1000.0,
+                cooling_time: PlayerCoolingTimer::COOLING_TIME,
+                collider_radius: 10.0,
+                hull: 100.0,
+                dynamics: FlightDynamics::default(),
+            },
+            Ship::Tanker => ShipStats {
+                fuel_capacity: 2000.0,
+                cooling_time: Duration::from_secs(12),
+                collider_radius: 14.0,
+                hull: 150.0,
+                dynamics: FlightDynamics {
+                    gravity_scale: 1.2,
+                    linear_damping: 0.1,
+                    max_speed: 1800.0,
+                    restitution: 0.1,
+                    thrust: 700.0,
+                    ..default()
+                },
+            },
+            Ship::Racer => ShipStats {
+                fuel_capacity: 700.0,
+                cooling_time: Duration::from_secs(6),
+                collider_radius: 8.0,
+                hull: 70.0,
+                dynamics: FlightDynamics {
+                    gravity_scale: 0.9,
+                    max_speed: 3200.0,
+                    restitution: 0.4,
+                    thrust: 1400.0,
+                    thrust_falloff: 0.3,
+                    ..default()
+                },
+            },
+        }
+    }
+    pub fn texture(&self, textures: &TextureAssets) -> Handle<Image> {
+        match self {
+            Ship::Standard => textures.spaceship.clone(),
+            Ship::Tanker => textures.tanker.clone(),
+            Ship::Racer => textures.racer.clone(),
+        }
+    }
+    /// animation frames flown in game, the plain texture is only shown in
+    /// menus
+    pub fn sheet(&self, sheets: &SheetAssets) -> Handle<TextureAtlas> {
+        match self {
+            Ship::Standard => sheets.spaceship.clone(),
+            Ship::Tanker => sheets.tanker.clone(),
+            Ship::Racer => sheets.racer.clone(),
+        }
+    }
+}
+#[derive(Component)]
+struct ShipSelectEntity;
+#[derive(Component)]
+struct ShipButton(Ship);
+#[derive(Component)]
+struct BackButton;
+fn spawn_ship_select(
+    mut commands: Commands,
+    fonts: Res<FontAssets>,
+    textures: Res<TextureAssets>,
+) {
+    commands
+        .spawn_bundle(Camera2dBundle::default())
+        .insert(ShipSelectEntity);
+    commands
+        .spawn_bundle(NodeBundle {
+            style: Style {
+                margin: UiRect::all(Val::Auto),
+                flex_direction: FlexDirection::ColumnReverse,
+                align_items: AlignItems::Center,
+                ..default()
+            },
+            color: colors::BUTTON_NORMAL_COLOR.into(),
+            ..default()
+        })
+        .insert(ShipSelectEntity)
+        .with_children(|parent| {
+            parent.spawn_bundle(TextBundle::from_section(
+                "CHOOSE YOUR SHIP",
+                TextStyle {
+                    font: fonts.silkscreen_bold.clone(),
+                    font_size: 60.0,
+                    color: Color::BLACK,
+                },
+            ));
+            for ship in Ship::ALL {
+                let stats = ship.stats();
+                parent
+                    .spawn_bundle(NodeBundle {
+                        style: Style {
+                            align_items: AlignItems::Center,
+                            ..default()
+                        },
+                        color: Color::NONE.into(),
+                        ..default()
+                    })
+                    .with_children(|parent| {
+                        parent.spawn_bundle(ImageBundle {
+                            style: Style {
+                                size: Size::new(Val::Px(64.0), Val::Px(64.0)),
+                                margin: UiRect::all(Val::Px(10.0)),
+                                ..default()
+                            },
+                            image: ship.texture(&textures).into(),
+                            ..default()
+                        });
+                        spawn_sized_button(
+                            parent,
+                            &fonts,
+                            &format!(
+                                "{} - FUEL {} COOLING {}S",
+                                ship.name(),
+                                stats.fuel_capacity,
+                                stats.cooling_time.as_secs()
+                            ),
+                            colors::SCORE_SIZE,
+                            ShipButton(ship),
+                        );
+                    });
+            }
+            spawn_sized_button(
+                parent,
+                &fonts,
+                "Back",
+                colors::SCORE_SIZE,
+                BackButton,
+            );
+        });
+}
+fn ship_buttons(
+    mut button_query: Query<(&mut UiColor, &Interaction, &ShipButton), ()>,
+    mut game_state: ResMut<State<GameState>>,
+    mut selected: ResMut<Ship>,
+) {
+    for (mut color, interaction, button) in button_query.iter_mut() {
+        if button_clicked(&mut color, interaction) {
+            *selected = button.0;
+            game_state.set(GameState::Game).unwrap();
+        }
+    }
+}
+fn back_button(
+    mut button_query: Query<(&mut UiColor, &Interaction), With<BackButton>>,
+    mut game_state: ResMut<State<GameState>>,
+) {
+    for (mut color, interaction) in button_query.iter_mut() {
+        if button_clicked(&mut color, interaction) {
+            game_state.set(GameState::MainMenu).unwrap();
+        }
+    }
+}
+fn clear_ship_select(
+    mut commands: Commands,
+    entity_query: Query<Entity, With<ShipSelectEntity>>,
+) {
+    for entity in entity_query.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+}
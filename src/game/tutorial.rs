@@ -0,0 +1,280 @@
+use super::{
+    spawn_fuel, Checkpoints, CoolingModel, FuelCurrentlySpawned, FuelPickedUp,
+    FuelTag, GameEntity, GameLabel, GameMode, GameState, PlayerCoolingTimer,
+    PlayerLabel, PlayerProfile,
+};
+use crate::loading::{FontAssets, SheetAssets};
+use bevy::prelude::*;
+
+pub struct TutorialPlugin;
+impl Plugin for TutorialPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(Tutorial::default())
+            .add_system_set(
+                SystemSet::on_enter(GameState::Game)
+                    .with_system(start_tutorial.after(GameLabel::Checkpoint)),
+            )
+            .add_system_set(
+                SystemSet::on_update(GameState::Game)
+                    .with_system(advance_tutorial)
+                    .with_system(place_pickups.after(advance_tutorial))
+                    .with_system(show_prompt.after(advance_tutorial)),
+            );
+    }
+}
+/// Steps of the tutorial in the order they are played
+#[derive(Clone, Copy, Eq, PartialEq, Debug)]
+pub enum TutorialStep {
+    ThrustUp,
+    CollectFuel,
+    WatchHeat,
+    CoolDown,
+    Done,
+}
+impl TutorialStep {
+    pub fn next(&self) -> Option<Self> {
+        match self {
+            TutorialStep::ThrustUp => Some(TutorialStep::CollectFuel),
+            TutorialStep::CollectFuel => Some(TutorialStep::WatchHeat),
+            TutorialStep::WatchHeat => Some(TutorialStep::CoolDown),
+            TutorialStep::CoolDown => Some(TutorialStep::Done),
+            TutorialStep::Done => None,
+        }
+    }
+    /// what the player is asked to do, `cooling_time` is the seconds of
+    /// cooling the ship starts with
+    pub fn prompt(
+        &self,
+        cooling_model: &CoolingModel,
+        cooling_time: f32,
+    ) -> String {
+        match (self, cooling_model) {
+            (TutorialStep::ThrustUp, _) => "HOLD W TO THRUST UP".to_string(),
+            (TutorialStep::CollectFuel, _) => {
+                "FLY INTO THE GREEN PICKUPS TO REFUEL".to_string()
+            }
+            (TutorialStep::WatchHeat, CoolingModel::Timer) => format!(
+                "THE RED TINT IS HEAT, COOLING RUNS OUT IN {} SECONDS",
+                cooling_time.round()
+            ),
+            (TutorialStep::WatchHeat, CoolingModel::Heat) => {
+                "THE RED TINT IS HEAT, THRUSTING HEATS THE SHIP".to_string()
+            }
+            (TutorialStep::CoolDown, CoolingModel::Timer) => {
+                "PICKUPS ALSO COOL THE SHIP, GRAB ONE BEFORE IT OVERHEATS"
+                    .to_string()
+            }
+            (TutorialStep::CoolDown, CoolingModel::Heat) => {
+                "COAST TO COOL DOWN, THEN GRAB ANOTHER PICKUP".to_string()
+            }
+            (TutorialStep::Done, _) => {
+                "YOU ARE READY, CLIMB AS HIGH AS YOU CAN".to_string()
+            }
+        }
+    }
+    /// steps that are finished by collecting a pickup
+    fn wants_pickup(&self) -> bool {
+        matches!(self, TutorialStep::CollectFuel | TutorialStep::CoolDown)
+    }
+    /// how long the prompt is shown before the step can finish
+    fn min_time(&self) -> f32 {
+        match self {
+            TutorialStep::Done => 4.0,
+            _ => 2.0,
+        }
+    }
+}
+/// Progress through the tutorial, `step` is `None` outside of it
+#[derive(Default)]
+pub struct Tutorial {
+    step: Option<TutorialStep>,
+    /// runs from the start of the step, it can not finish before this does
+    timer: Timer,
+    /// altitude the ship started the tutorial at
+    start_altitude: f32,
+    /// a pickup was collected since the step started
+    picked_up: bool,
+    /// pickups for the step still have to be placed
+    place_pickups: bool,
+}
+impl Tutorial {
+    /// height in pixels the ship has to climb in the first step
+    const THRUST_HEIGHT: f32 = 300.0;
+    /// fraction of cooling used that finishes the heat step
+    const WATCH_HEAT: f32 = 0.5;
+    /// where pickups are placed relative to the ship
+    const PICKUP_OFFSETS: [Vec2; 3] = [
+        Vec2::new(-120.0, 250.0),
+        Vec2::new(0.0, 350.0),
+        Vec2::new(120.0, 450.0),
+    ];
+    /// random pickups are held back while the tutorial places its own
+    pub fn places_pickups(&self) -> bool {
+        self.step.is_some()
+    }
+    fn start_step(&mut self, step: Option<TutorialStep>) {
+        self.step = step;
+        self.picked_up = false;
+        self.place_pickups = step.is_some_and(|step| step.wants_pickup());
+        if let Some(step) = step {
+            self.timer = Timer::from_seconds(step.min_time(), false);
+        }
+    }
+}
+/// Instructions for the current step
+#[derive(Component)]
+struct TutorialPrompt;
+mod colors {
+    use bevy::prelude::*;
+
+    pub const PROMPT_SIZE: f32 = 30.0;
+    pub const PROMPT_COLOR: Color = Color::rgb(1.0, 1.0, 1.0);
+    pub const PROMPT_BACKGROUND: Color = Color::rgba(0.0, 0.0, 0.0, 0.6);
+}
+fn start_tutorial(
+    mut commands: Commands,
+    mut tutorial: ResMut<Tutorial>,
+    mut profile: ResMut<PlayerProfile>,
+    game_mode: Res<GameMode>,
+    checkpoints: Res<Checkpoints>,
+    fonts: Res<FontAssets>,
+) {
+    *tutorial = Tutorial::default();
+    if *game_mode != GameMode::Tutorial {
+        return;
+    }
+    profile.mark_tutorial_seen();
+    tutorial.start_altitude = checkpoints.spawn_position().y;
+    tutorial.start_step(Some(TutorialStep::ThrustUp));
+    commands
+        .spawn_bundle(NodeBundle {
+            style: Style {
+                position_type: PositionType::Absolute,
+                position: UiRect {
+                    top: Val::Percent(28.0),
+                    left: Val::Percent(15.0),
+                    right: Val::Percent(15.0),
+                    ..default()
+                },
+                justify_content: JustifyContent::Center,
+                padding: UiRect::all(Val::Px(10.0)),
+                ..default()
+            },
+            color: colors::PROMPT_BACKGROUND.into(),
+            ..default()
+        })
+        .insert(GameEntity)
+        .with_children(|parent| {
+            parent
+                .spawn_bundle(TextBundle::from_section(
+                    "",
+                    TextStyle {
+                        font: fonts.silkscreen_bold.clone(),
+                        font_size: colors::PROMPT_SIZE,
+                        color: colors::PROMPT_COLOR,
+                    },
+                ))
+                .insert(TutorialPrompt);
+        });
+}
+fn advance_tutorial(
+    player_query: Query<(&Transform, &PlayerCoolingTimer), With<PlayerLabel>>,
+    mut pickup_events: EventReader<FuelPickedUp>,
+    mut tutorial: ResMut<Tutorial>,
+    time: Res<Time>,
+) {
+    let picked_up = pickup_events.iter().count() > 0;
+    let step = match tutorial.step {
+        Some(step) => step,
+        None => return,
+    };
+    let (transform, cooling) = match player_query.iter().next() {
+        Some(player) => player,
+        None => {
+            error!("player not found");
+            return;
+        }
+    };
+    tutorial.picked_up |= picked_up;
+    if !tutorial.timer.tick(time.delta()).finished() {
+        return;
+    }
+    let finished = match step {
+        TutorialStep::ThrustUp => {
+            transform.translation.y
+                >= tutorial.start_altitude + Tutorial::THRUST_HEIGHT
+        }
+        TutorialStep::CollectFuel | TutorialStep::CoolDown => {
+            tutorial.picked_up
+        }
+        TutorialStep::WatchHeat => {
+            cooling.get_frac_used() >= Tutorial::WATCH_HEAT
+        }
+        TutorialStep::Done => true,
+    };
+    if finished {
+        tutorial.start_step(step.next());
+    }
+}
+/// places the pickups of a step, and places them again if the ship has
+/// collected or left behind all of them without finishing the step
+fn place_pickups(
+    mut commands: Commands,
+    mut tutorial: ResMut<Tutorial>,
+    player_query: Query<&Transform, With<PlayerLabel>>,
+    fuel_query: Query<(), With<FuelTag>>,
+    mut fuel_spawn: ResMut<FuelCurrentlySpawned>,
+    sheets: Res<SheetAssets>,
+) {
+    let wants_pickup = tutorial
+        .step
+        .is_some_and(|step| step.wants_pickup() && !tutorial.picked_up);
+    if !wants_pickup || !(tutorial.place_pickups || fuel_query.is_empty()) {
+        return;
+    }
+    let player = match player_query.iter().next() {
+        Some(transform) => transform.translation.truncate(),
+        None => {
+            error!("player not found");
+            return;
+        }
+    };
+    for (frame, offset) in Tutorial::PICKUP_OFFSETS.iter().enumerate() {
+        spawn_fuel(
+            &mut commands,
+            &mut fuel_spawn,
+            &sheets,
+            player + *offset,
+            frame,
+        );
+    }
+    tutorial.place_pickups = false;
+}
+fn show_prompt(
+    player_query: Query<&PlayerCoolingTimer, With<PlayerLabel>>,
+    mut prompt_query: Query<(Entity, &mut Text, &Parent), With<TutorialPrompt>>,
+    mut visibility_query: Query<&mut Visibility>,
+    tutorial: Res<Tutorial>,
+    cooling_model: Res<CoolingModel>,
+) {
+    let cooling_time = player_query
+        .iter()
+        .next()
+        .map_or(0.0, |cooling| cooling.timer.duration().as_secs_f32());
+    for (entity, mut text, parent) in prompt_query.iter_mut() {
+        let prompt = tutorial
+            .step
+            .map(|step| step.prompt(&cooling_model, cooling_time));
+        // hidden along with its background once the tutorial is over
+        for entity in [entity, parent.get()] {
+            if let Ok(mut visibility) = visibility_query.get_mut(entity) {
+                visibility.is_visible = prompt.is_some();
+            }
+        }
+        if let Some(prompt) = prompt {
+            if text.sections[0].value != prompt {
+                text.sections[0].value = prompt;
+            }
+        }
+    }
+}
@@ -1,9 +1,12 @@
-use super::{GameEntity, GameState, MaxScore, PlayerCoolingTimer, PlayerFuel};
+use super::{
+    lerp_color, meters, ActiveModifiers, BiomeChanged, CheckpointReached,
+    Checkpoints, Combo, DailyChallenge, FuelPickedUp, GameEntity, GameMode,
+    GameState, HeatLevel, HeatWarning, Hull, MaxScore, NearMiss,
+    PlayerCoolingTimer, PlayerFuel, PlayerLabel, PlayerProfile,
+};
 use crate::game::PlayerScore;
 use crate::loading::FontAssets;
-use std::cmp::max;
 
-use bevy::ecs::schedule::ShouldRun::No;
 use bevy::prelude::*;
 
 pub struct UiPlugin;
@@ -14,21 +17,84 @@ struct GameOverMenuEntity;
 #[derive(Component)]
 struct ReturnButton;
 #[derive(Component)]
-struct FuelBar;
+struct MainMenuButton;
+/// picks the run back up from the last checkpoint
+#[derive(Component)]
+struct ContinueButton;
+#[derive(Component)]
+struct FuelBar {
+    /// runs after a pickup, the bar is drawn brighter until it finishes
+    flash: Timer,
+}
+impl FuelBar {
+    const FLASH_TIME_SEC: f32 = 0.3;
+}
+/// hull health, drawn in the shield color while shielded
+#[derive(Component)]
+struct HullBar;
+/// thinner bar under the [`FuelBar`] showing the reserve tank
+#[derive(Component)]
+struct ReserveBar;
 #[derive(Component)]
 struct ScoreTag;
+/// announces the active rule shift modifier
+#[derive(Component)]
+struct ModifierText;
+/// large announcement, faded out and removed once the timer finishes
+#[derive(Component)]
+struct Banner {
+    timer: Timer,
+}
+/// announces a new biome
+#[derive(Component)]
+struct BiomeBanner;
+impl BiomeBanner {
+    const SHOW_TIME_SEC: f32 = 3.0;
+}
+/// warns that the ship is close to overheating
+#[derive(Component)]
+struct HeatBanner;
+impl HeatBanner {
+    const SHOW_TIME_SEC: f32 = 2.0;
+}
+/// announces a saved checkpoint
+#[derive(Component)]
+struct CheckpointBanner;
+impl CheckpointBanner {
+    const SHOW_TIME_SEC: f32 = 2.0;
+}
+/// shows the bonus for flying close by a hazard
+#[derive(Component)]
+struct NearMissBanner;
+impl NearMissBanner {
+    const SHOW_TIME_SEC: f32 = 1.0;
+}
 #[derive(Component)]
 pub struct GameOverMaxScore;
-mod colors {
+pub(super) mod colors {
     use bevy::prelude::*;
 
     pub const BUTTON_NORMAL_COLOR: Color = Color::rgb(0.3, 0.3, 0.3);
     pub const BUTTON_HOVERD_COLOR: Color = Color::rgb(0.4, 0.4, 0.4);
     pub const BUTTON_CLICKED_COLOR: Color = Color::rgb(0.6, 0.6, 0.6);
     pub const SCORE_SIZE: f32 = 50.0;
+    pub const MODIFIER_SIZE: f32 = 40.0;
+    pub const MODIFIER_COLOR: Color = Color::rgb(1.0, 1.0, 0.0);
+    pub const BANNER_SIZE: f32 = 60.0;
+    pub const BANNER_COLOR: Color = Color::rgb(1.0, 1.0, 1.0);
+    pub const HEAT_HOT_COLOR: Color = Color::rgb(1.0, 0.6, 0.1);
+    pub const HEAT_CRITICAL_COLOR: Color = Color::rgb(1.0, 0.1, 0.1);
+    pub const CHECKPOINT_COLOR: Color = Color::rgb(0.4, 1.0, 0.5);
+    pub const NEAR_MISS_COLOR: Color = Color::rgb(1.0, 0.9, 0.2);
+    pub const COMBO_COLOR: Color = Color::rgb(1.0, 0.5, 0.0);
     pub const FUEL_BAR_SIZE: Val = Val::Px(100.0);
     pub const FUEL_BAR_COLOR: Color = Color::rgb(0.0, 1.0, 0.0);
-    pub const MAX_SCORE_SIZE: f32 = 50.0;
+    pub const FUEL_BAR_FLASH_COLOR: Color = Color::rgb(1.0, 1.0, 1.0);
+    pub const HULL_BAR_SIZE: Val = Val::Px(25.0);
+    pub const HULL_BAR_COLOR: Color = Color::rgb(0.9, 0.2, 0.2);
+    pub const HULL_BAR_SHIELD_COLOR: Color = Color::rgb(0.3, 0.8, 1.0);
+    pub const RESERVE_BAR_SIZE: Val = Val::Px(25.0);
+    pub const RESERVE_BAR_COLOR: Color = Color::rgb(1.0, 0.6, 0.0);
 }
 impl Plugin for UiPlugin {
     fn build(&self, app: &mut App) {
@@ -36,7 +102,16 @@ impl Plugin for UiPlugin {
             SystemSet::on_enter(GameState::Game).with_system(spawn_game_ui),
         )
         .add_system_set(
-            SystemSet::on_update(GameState::Game).with_system(ui_run),
+            SystemSet::on_update(GameState::Game)
+                .with_system(ui_run)
+                .with_system(modifier_hud)
+                .with_system(flash_fuel_bar)
+                .with_system(update_hull_bar)
+                .with_system(spawn_biome_banner)
+                .with_system(spawn_heat_banner)
+                .with_system(spawn_checkpoint_banner)
+                .with_system(spawn_near_miss_banner)
+                .with_system(fade_banner),
         )
         .add_system_set(
             SystemSet::on_enter(GameState::GameOver)
@@ -44,7 +119,9 @@ impl Plugin for UiPlugin {
         )
         .add_system_set(
             SystemSet::on_update(GameState::GameOver)
-                .with_system(return_button),
+                .with_system(return_button)
+                .with_system(continue_button)
+                .with_system(main_menu_button),
         )
         .add_system_set(
             SystemSet::on_exit(GameState::GameOver).with_system(clear_gameover),
@@ -85,7 +162,7 @@ fn spawn_game_ui(mut commands: Commands, fonts: Res<FontAssets>) {
                         TextStyle {
                             font: fonts.silkscreen.clone(),
                             font_size: colors::SCORE_SIZE,
-                            color: Color::BLACK.into(),
+                            color: Color::BLACK,
                         },
                     )
                     .with_style(Style {
@@ -95,6 +172,16 @@ fn spawn_game_ui(mut commands: Commands, fonts: Res<FontAssets>) {
                 )
                 .insert(ScoreTag)
                 .insert(GameEntity);
+            parent
+                .spawn_bundle(TextBundle::from_section(
+                    "",
+                    TextStyle {
+                        font: fonts.silkscreen_bold.clone(),
+                        font_size: colors::MODIFIER_SIZE,
+                        color: colors::MODIFIER_COLOR,
+                    },
+                ))
+                .insert(ModifierText);
             parent
                 .spawn_bundle(NodeBundle {
                     style: Style {
@@ -107,14 +194,49 @@ fn spawn_game_ui(mut commands: Commands, fonts: Res<FontAssets>) {
                     color: colors::FUEL_BAR_COLOR.into(),
                     ..default()
                 })
-                .insert(FuelBar);
+                .insert(FuelBar {
+                    flash: Timer::from_seconds(0.0, false),
+                });
+            parent
+                .spawn_bundle(NodeBundle {
+                    style: Style {
+                        size: Size::new(
+                            Val::Percent(0.0),
+                            colors::RESERVE_BAR_SIZE,
+                        ),
+                        ..default()
+                    },
+                    color: colors::RESERVE_BAR_COLOR.into(),
+                    ..default()
+                })
+                .insert(ReserveBar);
+            parent
+                .spawn_bundle(NodeBundle {
+                    style: Style {
+                        size: Size::new(
+                            Val::Percent(80.0),
+                            colors::HULL_BAR_SIZE,
+                        ),
+                        ..default()
+                    },
+                    color: colors::HULL_BAR_COLOR.into(),
+                    ..default()
+                })
+                .insert(HullBar);
         });
 }
+#[allow(clippy::type_complexity)]
 fn ui_run(
-    player_query: Query<(&PlayerCoolingTimer, &PlayerFuel, &PlayerScore), ()>,
+    player_query: Query<
+        (&PlayerCoolingTimer, &PlayerFuel, &PlayerScore, &Combo),
+        (),
+    >,
     mut cooling_query: Query<&mut UiColor, With<CoolingOverlay>>,
     mut text_query: Query<&mut Text, With<ScoreTag>>,
-    mut fuel_bar_query: Query<&mut Style, With<FuelBar>>,
+    mut bar_query: ParamSet<(
+        Query<&mut Style, With<FuelBar>>,
+        Query<&mut Style, With<ReserveBar>>,
+    )>,
     fonts: Res<FontAssets>,
 ) {
     let player = player_query.iter().next();
@@ -122,29 +244,281 @@ fn ui_run(
         error!("player does not exist");
         return;
     }
-    let (player, player_fuel, player_score) = player.unwrap();
+    let (player, player_fuel, player_score, combo) = player.unwrap();
     for mut color in cooling_query.iter_mut() {
         *color = Color::rgba(1.0, 0.0, 0.0, player.get_frac_used()).into();
     }
-    for mut fuel_bar_style in fuel_bar_query.iter_mut() {
+    for mut fuel_bar_style in bar_query.p0().iter_mut() {
         fuel_bar_style.size.width =
             Val::Percent(100.0 * player_fuel.get_fuel_ratio_left());
     }
+    for mut reserve_bar_style in bar_query.p1().iter_mut() {
+        reserve_bar_style.size.width =
+            Val::Percent(80.0 * player_fuel.get_reserve_ratio_left());
+    }
     for mut text in text_query.iter_mut() {
         text.sections = vec![TextSection::new(
             format!("{}", player_score.get_score()),
             TextStyle {
                 font: fonts.silkscreen.clone(),
                 font_size: colors::SCORE_SIZE,
-                color: Color::BLACK.into(),
+                color: Color::BLACK,
             },
-        )]
+        )];
+        if combo.get_multiplier() > 1 {
+            text.sections.push(TextSection::new(
+                format!(" x{}", combo.get_multiplier()),
+                TextStyle {
+                    font: fonts.silkscreen_bold.clone(),
+                    font_size: colors::SCORE_SIZE,
+                    color: colors::COMBO_COLOR,
+                },
+            ));
+        }
+    }
+}
+fn update_hull_bar(
+    player_query: Query<&Hull, With<PlayerLabel>>,
+    mut hull_bar_query: Query<(&mut Style, &mut UiColor), With<HullBar>>,
+) {
+    let hull = match player_query.iter().next() {
+        Some(hull) => hull,
+        None => {
+            error!("player not found");
+            return;
+        }
+    };
+    for (mut style, mut color) in hull_bar_query.iter_mut() {
+        style.size.width = Val::Percent(80.0 * hull.get_health_ratio_left());
+        *color = if hull.is_shielded() {
+            colors::HULL_BAR_SHIELD_COLOR
+        } else {
+            colors::HULL_BAR_COLOR
+        }
+        .into();
+    }
+}
+fn flash_fuel_bar(
+    mut pickup_events: EventReader<FuelPickedUp>,
+    mut fuel_bar_query: Query<(&mut FuelBar, &mut UiColor), ()>,
+    time: Res<Time>,
+) {
+    let picked_up = pickup_events.iter().count() > 0;
+    for (mut fuel_bar, mut color) in fuel_bar_query.iter_mut() {
+        if picked_up {
+            fuel_bar.flash =
+                Timer::from_seconds(FuelBar::FLASH_TIME_SEC, false);
+        }
+        fuel_bar.flash.tick(time.delta());
+        let t = fuel_bar.flash.percent_left();
+        *color =
+            lerp_color(colors::FUEL_BAR_COLOR, colors::FUEL_BAR_FLASH_COLOR, t)
+                .into();
+    }
+}
+fn modifier_hud(
+    modifiers: Res<ActiveModifiers>,
+    mut text_query: Query<&mut Text, With<ModifierText>>,
+) {
+    if !modifiers.is_enabled() {
+        return;
+    }
+    let countdown = modifiers.get_time_left().ceil();
+    let message = match modifiers.get_active() {
+        Some(modifier) => {
+            format!("{} - NEXT IN {}", modifier.name(), countdown)
+        }
+        None => format!("RULES SHIFT IN {}", countdown),
+    };
+    for mut text in text_query.iter_mut() {
+        text.sections[0].value = message.clone();
+    }
+}
+fn spawn_biome_banner(
+    mut commands: Commands,
+    mut biome_events: EventReader<BiomeChanged>,
+    fonts: Res<FontAssets>,
+    old_banners: Query<Entity, With<BiomeBanner>>,
+) {
+    let biome = match biome_events.iter().last() {
+        Some(event) => event.0,
+        None => return,
+    };
+    for entity in old_banners.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+    commands
+        .spawn_bundle(
+            TextBundle::from_section(
+                format!("ENTERING {}", biome.name()),
+                TextStyle {
+                    font: fonts.silkscreen_bold.clone(),
+                    font_size: colors::BANNER_SIZE,
+                    color: colors::BANNER_COLOR,
+                },
+            )
+            .with_style(Style {
+                position_type: PositionType::Absolute,
+                position: UiRect {
+                    top: Val::Percent(25.0),
+                    left: Val::Percent(10.0),
+                    ..default()
+                },
+                ..default()
+            }),
+        )
+        .insert(BiomeBanner)
+        .insert(Banner {
+            timer: Timer::from_seconds(BiomeBanner::SHOW_TIME_SEC, false),
+        })
+        .insert(GameEntity);
+}
+fn spawn_heat_banner(
+    mut commands: Commands,
+    mut heat_warnings: EventReader<HeatWarning>,
+    fonts: Res<FontAssets>,
+    old_banners: Query<Entity, With<HeatBanner>>,
+) {
+    let (message, color) = match heat_warnings.iter().last() {
+        Some(HeatWarning(HeatLevel::Hot)) => {
+            ("OVERHEATING", colors::HEAT_HOT_COLOR)
+        }
+        Some(HeatWarning(HeatLevel::Critical)) => {
+            ("CRITICAL HEAT", colors::HEAT_CRITICAL_COLOR)
+        }
+        Some(HeatWarning(HeatLevel::Normal)) | None => return,
+    };
+    for entity in old_banners.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+    commands
+        .spawn_bundle(
+            TextBundle::from_section(
+                message,
+                TextStyle {
+                    font: fonts.silkscreen_bold.clone(),
+                    font_size: colors::BANNER_SIZE,
+                    color,
+                },
+            )
+            .with_style(Style {
+                position_type: PositionType::Absolute,
+                position: UiRect {
+                    top: Val::Percent(40.0),
+                    left: Val::Percent(10.0),
+                    ..default()
+                },
+                ..default()
+            }),
+        )
+        .insert(HeatBanner)
+        .insert(Banner {
+            timer: Timer::from_seconds(HeatBanner::SHOW_TIME_SEC, false),
+        })
+        .insert(GameEntity);
+}
+fn spawn_checkpoint_banner(
+    mut commands: Commands,
+    mut reached_events: EventReader<CheckpointReached>,
+    fonts: Res<FontAssets>,
+    old_banners: Query<Entity, With<CheckpointBanner>>,
+) {
+    let reached = match reached_events.iter().last() {
+        Some(reached) => reached,
+        None => return,
+    };
+    for entity in old_banners.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+    commands
+        .spawn_bundle(
+            TextBundle::from_section(
+                format!("CHECKPOINT {} m", meters(reached.altitude)),
+                TextStyle {
+                    font: fonts.silkscreen_bold.clone(),
+                    font_size: colors::BANNER_SIZE,
+                    color: colors::CHECKPOINT_COLOR,
+                },
+            )
+            .with_style(Style {
+                position_type: PositionType::Absolute,
+                position: UiRect {
+                    top: Val::Percent(55.0),
+                    left: Val::Percent(10.0),
+                    ..default()
+                },
+                ..default()
+            }),
+        )
+        .insert(CheckpointBanner)
+        .insert(Banner {
+            timer: Timer::from_seconds(CheckpointBanner::SHOW_TIME_SEC, false),
+        })
+        .insert(GameEntity);
+}
+fn spawn_near_miss_banner(
+    mut commands: Commands,
+    mut near_miss_events: EventReader<NearMiss>,
+    fonts: Res<FontAssets>,
+    old_banners: Query<Entity, With<NearMissBanner>>,
+) {
+    let near_miss = match near_miss_events.iter().last() {
+        Some(near_miss) => near_miss,
+        None => return,
+    };
+    for entity in old_banners.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+    commands
+        .spawn_bundle(
+            TextBundle::from_section(
+                format!("NEAR MISS +{}", near_miss.points),
+                TextStyle {
+                    font: fonts.silkscreen_bold.clone(),
+                    font_size: colors::BANNER_SIZE,
+                    color: colors::NEAR_MISS_COLOR,
+                },
+            )
+            .with_style(Style {
+                position_type: PositionType::Absolute,
+                position: UiRect {
+                    top: Val::Percent(70.0),
+                    left: Val::Percent(10.0),
+                    ..default()
+                },
+                ..default()
+            }),
+        )
+        .insert(NearMissBanner)
+        .insert(Banner {
+            timer: Timer::from_seconds(NearMissBanner::SHOW_TIME_SEC, false),
+        })
+        .insert(GameEntity);
+}
+fn fade_banner(
+    mut commands: Commands,
+    mut banner_query: Query<(Entity, &mut Banner, &mut Text), ()>,
+    time: Res<Time>,
+) {
+    for (entity, mut banner, mut text) in banner_query.iter_mut() {
+        banner.timer.tick(time.delta());
+        if banner.timer.finished() {
+            commands.entity(entity).despawn_recursive();
+            continue;
+        }
+        for section in text.sections.iter_mut() {
+            section.style.color.set_a(banner.timer.percent_left());
+        }
     }
 }
 fn spawn_game_over(
     mut commands: Commands,
     fonts: Res<FontAssets>,
     max_score: Res<MaxScore>,
+    game_mode: Res<GameMode>,
+    daily: Res<DailyChallenge>,
+    profile: Res<PlayerProfile>,
+    checkpoints: Res<Checkpoints>,
 ) {
     commands
         .spawn_bundle(Camera2dBundle::default())
@@ -168,57 +542,162 @@ fn spawn_game_over(
                     TextStyle {
                         font: fonts.silkscreen_bold.clone(),
                         font_size: 80.0,
-                        color: Color::rgb(0.0, 0.0, 0.0).into(),
+                        color: Color::rgb(0.0, 0.0, 0.0),
                     },
                 ))
                 .insert(GameOverMenuEntity);
             parent.spawn_bundle(TextBundle::from_section(
-                format!("HIGH SCORE: {}", max_score.score.get_score()),
+                format!(
+                    "HIGH SCORE: {} ({})",
+                    max_score.score.get_score(),
+                    max_score.ship.name()
+                ),
                 TextStyle {
                     font: fonts.silkscreen.clone(),
                     font_size: colors::SCORE_SIZE,
-                    color: Color::BLACK.into(),
+                    color: Color::BLACK,
                 },
             ));
-            parent
-                .spawn_bundle(ButtonBundle {
-                    style: Style {
-                        margin: UiRect::all(Val::Auto),
-                        justify_content: JustifyContent::Center,
-                        align_items: AlignItems::Center,
-                        ..default()
+            if *game_mode == GameMode::DailyChallenge {
+                parent.spawn_bundle(TextBundle::from_section(
+                    format!(
+                        "DAILY {} BEST: {} ({})",
+                        daily.date(),
+                        daily.get_best().get_score(),
+                        daily.get_best_ship().name()
+                    ),
+                    TextStyle {
+                        font: fonts.silkscreen.clone(),
+                        font_size: colors::SCORE_SIZE,
+                        color: Color::BLACK,
                     },
-                    color: Color::rgb(0.6, 0.6, 0.6).into(),
-                    ..default()
-                })
-                .insert(ReturnButton)
-                .insert(GameOverMenuEntity)
-                .with_children(|parent| {
-                    parent
-                        .spawn_bundle(TextBundle::from_section(
-                            "Start New Game",
-                            TextStyle {
-                                font: fonts.silkscreen.clone(),
-                                font_size: 80.0,
-                                color: Color::rgb(0.0, 0.0, 0.0).into(),
-                            },
-                        ))
-                        .insert(GameOverMenuEntity);
-                });
+                ));
+            }
+            for skin in profile.get_newly_unlocked() {
+                parent.spawn_bundle(TextBundle::from_section(
+                    format!("SKIN UNLOCKED: {}", skin.name()),
+                    TextStyle {
+                        font: fonts.silkscreen_bold.clone(),
+                        font_size: colors::SCORE_SIZE,
+                        color: colors::MODIFIER_COLOR,
+                    },
+                ));
+            }
+            // the daily challenge only has one scored attempt
+            if checkpoints.can_continue()
+                && *game_mode != GameMode::DailyChallenge
+            {
+                spawn_button(
+                    parent,
+                    &fonts,
+                    &format!("Continue (-{})", Checkpoints::CONTINUE_PENALTY),
+                    ContinueButton,
+                );
+            }
+            spawn_button(parent, &fonts, "Start New Game", ReturnButton);
+            spawn_button(parent, &fonts, "Main Menu", MainMenuButton);
+        });
+}
+/// spawns a text button, `marker` is used to find the button's interactions
+pub(super) fn spawn_button(
+    parent: &mut ChildBuilder,
+    fonts: &FontAssets,
+    text: &str,
+    marker: impl Component,
+) {
+    spawn_sized_button(parent, fonts, text, 80.0, marker);
+}
+/// spawns a text button with the given font size, the text is the button's
+/// only child
+pub(super) fn spawn_sized_button(
+    parent: &mut ChildBuilder,
+    fonts: &FontAssets,
+    text: &str,
+    font_size: f32,
+    marker: impl Component,
+) {
+    parent
+        .spawn_bundle(ButtonBundle {
+            style: Style {
+                margin: UiRect::all(Val::Auto),
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                ..default()
+            },
+            color: Color::rgb(0.6, 0.6, 0.6).into(),
+            ..default()
+        })
+        .insert(marker)
+        .with_children(|parent| {
+            parent.spawn_bundle(TextBundle::from_section(
+                text,
+                TextStyle {
+                    font: fonts.silkscreen.clone(),
+                    font_size,
+                    color: Color::rgb(0.0, 0.0, 0.0),
+                },
+            ));
         });
 }
+/// highlights a button and returns true when it was clicked
+pub(super) fn button_clicked(
+    color: &mut UiColor,
+    interaction: &Interaction,
+) -> bool {
+    match *interaction {
+        Interaction::Clicked => {
+            *color = colors::BUTTON_CLICKED_COLOR.into();
+            true
+        }
+        Interaction::Hovered => {
+            *color = colors::BUTTON_HOVERD_COLOR.into();
+            false
+        }
+        Interaction::None => {
+            *color = colors::BUTTON_NORMAL_COLOR.into();
+            false
+        }
+    }
+}
 fn return_button(
     mut color_query: Query<(&mut UiColor, &Interaction), With<ReturnButton>>,
     mut game_state: ResMut<State<GameState>>,
+    mut game_mode: ResMut<GameMode>,
 ) {
     for (mut color, interaction) in color_query.iter_mut() {
-        match *interaction {
-            Interaction::Clicked => {
-                *color = colors::BUTTON_CLICKED_COLOR.into();
-                game_state.set(GameState::Game).unwrap()
+        if button_clicked(&mut color, interaction) {
+            // the daily challenge only has one scored attempt and the
+            // tutorial is followed by a regular run
+            if matches!(
+                *game_mode,
+                GameMode::DailyChallenge | GameMode::Tutorial
+            ) {
+                *game_mode = GameMode::Classic;
             }
-            Interaction::Hovered => *color = colors::BUTTON_HOVERD_COLOR.into(),
-            Interaction::None => *color = colors::BUTTON_NORMAL_COLOR.into(),
+            // a new game can be started with a different ship
+            game_state.set(GameState::ShipSelect).unwrap()
+        }
+    }
+}
+fn continue_button(
+    mut color_query: Query<(&mut UiColor, &Interaction), With<ContinueButton>>,
+    mut game_state: ResMut<State<GameState>>,
+    mut checkpoints: ResMut<Checkpoints>,
+) {
+    for (mut color, interaction) in color_query.iter_mut() {
+        if button_clicked(&mut color, interaction) {
+            checkpoints.resume();
+            game_state.set(GameState::Game).unwrap()
+        }
+    }
+}
+fn main_menu_button(
+    mut color_query: Query<(&mut UiColor, &Interaction), With<MainMenuButton>>,
+    mut game_state: ResMut<State<GameState>>,
+) {
+    for (mut color, interaction) in color_query.iter_mut() {
+        if button_clicked(&mut color, interaction) {
+            game_state.set(GameState::MainMenu).unwrap()
         }
     }
 }
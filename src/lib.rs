@@ -1,5 +1,6 @@
 mod game;
 mod loading;
+mod storage;
 
 use crate::CursorIcon::Default;
 use bevy::{
@@ -13,6 +14,9 @@ use loading::{LoadingPlugin, TextureAssets};
 #[derive(Clone, Copy, Eq, PartialEq, Debug, Hash)]
 pub enum GameState {
     Loading,
+    MainMenu,
+    /// picking a ship before a run
+    ShipSelect,
     Game,
     GameOver,
 }
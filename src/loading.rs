@@ -1,4 +1,4 @@
-use crate::GameState;
+use crate::{game::AnimationClips, GameState};
 use bevy::prelude::*;
 use bevy_asset_loader::prelude::*;
 
@@ -7,8 +7,11 @@ impl Plugin for LoadingPlugin {
     fn build(&self, app: &mut App) {
         app.add_state(GameState::Loading).add_loading_state(
             LoadingState::new(GameState::Loading)
-                .continue_to_state(GameState::Game)
+                .continue_to_state(GameState::MainMenu)
                 .with_collection::<TextureAssets>()
+                .with_collection::<SheetAssets>()
+                .with_collection::<SkinAssets>()
+                .with_collection::<AnimationAssets>()
                 .with_collection::<FontAssets>(),
         );
     }
@@ -19,11 +22,130 @@ pub struct TextureAssets {
     //pub texture: Handle<Image>,
     #[asset(path = "textures/spaceship.png")]
     pub spaceship: Handle<Image>,
-    #[asset(path = "textures/fuel.png")]
-    pub fuel: Handle<Image>,
+    #[asset(path = "textures/tanker.png")]
+    pub tanker: Handle<Image>,
+    #[asset(path = "textures/racer.png")]
+    pub racer: Handle<Image>,
     #[asset(path = "textures/grass.png")]
     pub grass: Handle<Image>,
 }
+/// animation frames of the ships and pickups, see `animations/clips.anim`
+#[derive(AssetCollection)]
+pub struct SheetAssets {
+    #[asset(texture_atlas(
+        tile_size_x = 16.,
+        tile_size_y = 20.,
+        columns = 8,
+        rows = 1
+    ))]
+    #[asset(path = "textures/sheets/spaceship.png")]
+    pub spaceship: Handle<TextureAtlas>,
+    #[asset(texture_atlas(
+        tile_size_x = 16.,
+        tile_size_y = 20.,
+        columns = 8,
+        rows = 1
+    ))]
+    #[asset(path = "textures/sheets/tanker.png")]
+    pub tanker: Handle<TextureAtlas>,
+    #[asset(texture_atlas(
+        tile_size_x = 16.,
+        tile_size_y = 20.,
+        columns = 8,
+        rows = 1
+    ))]
+    #[asset(path = "textures/sheets/racer.png")]
+    pub racer: Handle<TextureAtlas>,
+    #[asset(texture_atlas(
+        tile_size_x = 8.,
+        tile_size_y = 8.,
+        columns = 12,
+        rows = 1
+    ))]
+    #[asset(path = "textures/sheets/fuel.png")]
+    pub fuel: Handle<TextureAtlas>,
+}
+/// palette swapped ship sheets, named skin_ship
+#[derive(AssetCollection)]
+pub struct SkinAssets {
+    #[asset(texture_atlas(
+        tile_size_x = 16.,
+        tile_size_y = 20.,
+        columns = 8,
+        rows = 1
+    ))]
+    #[asset(path = "textures/sheets/ember_standard.png")]
+    pub ember_standard: Handle<TextureAtlas>,
+    #[asset(texture_atlas(
+        tile_size_x = 16.,
+        tile_size_y = 20.,
+        columns = 8,
+        rows = 1
+    ))]
+    #[asset(path = "textures/sheets/ember_tanker.png")]
+    pub ember_tanker: Handle<TextureAtlas>,
+    #[asset(texture_atlas(
+        tile_size_x = 16.,
+        tile_size_y = 20.,
+        columns = 8,
+        rows = 1
+    ))]
+    #[asset(path = "textures/sheets/ember_racer.png")]
+    pub ember_racer: Handle<TextureAtlas>,
+    #[asset(texture_atlas(
+        tile_size_x = 16.,
+        tile_size_y = 20.,
+        columns = 8,
+        rows = 1
+    ))]
+    #[asset(path = "textures/sheets/arctic_standard.png")]
+    pub arctic_standard: Handle<TextureAtlas>,
+    #[asset(texture_atlas(
+        tile_size_x = 16.,
+        tile_size_y = 20.,
+        columns = 8,
+        rows = 1
+    ))]
+    #[asset(path = "textures/sheets/arctic_tanker.png")]
+    pub arctic_tanker: Handle<TextureAtlas>,
+    #[asset(texture_atlas(
+        tile_size_x = 16.,
+        tile_size_y = 20.,
+        columns = 8,
+        rows = 1
+    ))]
+    #[asset(path = "textures/sheets/arctic_racer.png")]
+    pub arctic_racer: Handle<TextureAtlas>,
+    #[asset(texture_atlas(
+        tile_size_x = 16.,
+        tile_size_y = 20.,
+        columns = 8,
+        rows = 1
+    ))]
+    #[asset(path = "textures/sheets/gold_standard.png")]
+    pub gold_standard: Handle<TextureAtlas>,
+    #[asset(texture_atlas(
+        tile_size_x = 16.,
+        tile_size_y = 20.,
+        columns = 8,
+        rows = 1
+    ))]
+    #[asset(path = "textures/sheets/gold_tanker.png")]
+    pub gold_tanker: Handle<TextureAtlas>,
+    #[asset(texture_atlas(
+        tile_size_x = 16.,
+        tile_size_y = 20.,
+        columns = 8,
+        rows = 1
+    ))]
+    #[asset(path = "textures/sheets/gold_racer.png")]
+    pub gold_racer: Handle<TextureAtlas>,
+}
+#[derive(AssetCollection)]
+pub struct AnimationAssets {
+    #[asset(path = "animations/clips.anim")]
+    pub clips: Handle<AnimationClips>,
+}
 #[derive(AssetCollection)]
 pub struct FontAssets {
     #[asset(path = "fonts/Silkscreen-Bold.ttf")]
@@ -0,0 +1,90 @@
+//! Small key/value persistence. Native builds write plain text files into
+//! `save/`, the wasm build uses the browser's local storage.
+use bevy::prelude::*;
+use std::collections::HashMap;
+
+const SECONDS_PER_DAY: u64 = 60 * 60 * 24;
+
+/// loads the value previously saved under `key`
+pub fn load(key: &str) -> Option<String> {
+    platform::load(key)
+}
+/// saves `value` under `key`, failures are logged and otherwise ignored
+pub fn save(key: &str, value: &str) {
+    if let Err(e) = platform::save(key, value) {
+        error!("failed to save {}: {}", key, e);
+    }
+}
+/// parses `key=value` lines as written by [`write_fields`]
+pub fn parse_fields(data: &str) -> HashMap<&str, &str> {
+    data.lines()
+        .filter_map(|line| line.split_once('='))
+        .map(|(key, value)| (key.trim(), value.trim()))
+        .collect()
+}
+pub fn write_fields(fields: &[(&str, String)]) -> String {
+    fields
+        .iter()
+        .map(|(key, value)| format!("{}={}\n", key, value))
+        .collect()
+}
+/// number of whole days since the unix epoch in UTC
+pub fn utc_day() -> u64 {
+    platform::unix_seconds() / SECONDS_PER_DAY
+}
+/// converts days since the unix epoch into a (year, month, day) civil date
+pub fn civil_from_days(days: u64) -> (i64, u32, u32) {
+    let z = days as i64 + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = (doy - (153 * mp + 2) / 5 + 1) as u32;
+    let month = (if mp < 10 { mp + 3 } else { mp - 9 }) as u32;
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    (year, month, day)
+}
+#[cfg(not(target_arch = "wasm32"))]
+mod platform {
+    use std::{
+        fs, io,
+        path::PathBuf,
+        time::{SystemTime, UNIX_EPOCH},
+    };
+    const SAVE_DIR: &str = "save";
+    fn path(key: &str) -> PathBuf {
+        PathBuf::from(SAVE_DIR).join(format!("{}.txt", key))
+    }
+    pub fn load(key: &str) -> Option<String> {
+        fs::read_to_string(path(key)).ok()
+    }
+    pub fn save(key: &str, value: &str) -> io::Result<()> {
+        fs::create_dir_all(SAVE_DIR)?;
+        fs::write(path(key), value)
+    }
+    pub fn unix_seconds() -> u64 {
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or(0)
+    }
+}
+#[cfg(target_arch = "wasm32")]
+mod platform {
+    fn local_storage() -> Option<web_sys::Storage> {
+        web_sys::window()?.local_storage().ok()?
+    }
+    pub fn load(key: &str) -> Option<String> {
+        local_storage()?.get_item(key).ok()?
+    }
+    pub fn save(key: &str, value: &str) -> Result<(), String> {
+        local_storage()
+            .ok_or_else(|| "local storage unavailable".to_string())?
+            .set_item(key, value)
+            .map_err(|e| format!("{:?}", e))
+    }
+    pub fn unix_seconds() -> u64 {
+        (js_sys::Date::now() / 1000.0) as u64
+    }
+}